@@ -0,0 +1,124 @@
+use std::sync::mpsc::{self, Receiver};
+
+use super::graph::{is_running, EXIT_POLL};
+use super::EcstaticSystems;
+
+impl EcstaticSystems {
+    /// Signals every handle in a category once and returns one receiver per handle, in registration order,
+    /// which gets a message once the worker has finished processing that signal.
+    /// A receiver disconnects without a message if its handle could not be signaled or its worker exits first.
+    /// Returns no receivers for a category which is not registered.
+    pub fn signal_all_acked(&self, category: &str) -> Vec<Receiver<()>> {
+        let ths = match self.handles.get(category) {
+            Some(ths) => ths,
+            None => return Vec::new(),
+        };
+        let frozen = self.is_frozen();
+        ths.iter().enumerate()
+            .map(|(index, th)| {
+                let target = if frozen { None } else { self.signal_handle(category, index, th) };
+                match target {
+                    Some(target) => th.state.ack_at(target),
+                    None => mpsc::channel().1,
+                }
+            })
+            .collect()
+    }
+
+    /// Blocks until one of `categories` has finished every signal delivered to it so far, and returns its name.
+    /// When several finish together the one listed first wins. A category which is not registered, has nothing
+    /// left to run or whose workers have all exited finishes straight away.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `categories` is empty.
+    pub fn wait_any(&self, categories: &[&str]) -> String {
+        assert!(!categories.is_empty(), "wait_any needs at least one category");
+        let (ack, acked) = mpsc::channel();
+        let targets: Vec<Vec<_>> = categories.iter()
+            .map(|category| {
+                self.handles.get(*category).into_iter()
+                    .flatten()
+                    .map(|th| {
+                        let target = th.state.signaled_count();
+                        th.state.notify_at(target, ack.clone());
+                        (th, target)
+                    })
+                    .collect()
+            })
+            .collect();
+        loop {
+            let finished = targets.iter().position(|ths| ths.iter().all(|(th, target)| !is_running(th, *target)));
+            if let Some(finished) = finished {
+                return String::from(categories[finished]);
+            }
+            //workers which exit drop their acks without a message, so they are only noticed by polling.
+            let _ = acked.recv_timeout(EXIT_POLL);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc::RecvTimeoutError;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::test_util::DEFAULT_TIMEOUT;
+    use crate::EcstaticSystems;
+
+    #[test]
+    fn test_signal_all_acked() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMICS: [AtomicUsize; 3] = [const { AtomicUsize::new(0) }; 3];
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        for atomic in ATOMICS.iter() {
+            sys.register_static("testing", atomic, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        }
+        sys.register_self_terminating("exits", &ATOMIC, |x| x.fetch_add(1, Ordering::SeqCst) == 0);
+        sys.signal("testing");
+        let acks = sys.signal_all_acked("testing");
+        assert!(acks.len() == 3, "Actual: {:?}", acks.len());
+        for (ack, atomic) in acks.iter().zip(ATOMICS.iter()) {
+            assert!(ack.recv_timeout(DEFAULT_TIMEOUT) == Ok(()));
+            assert!(atomic.load(Ordering::SeqCst) == 2, "Actual: {:?}", atomic.load(Ordering::SeqCst));
+        }
+
+        sys.signal("exits");
+        sys.signal("exits");
+        let acks = sys.signal_all_acked("exits");
+        assert!(acks[0].recv_timeout(DEFAULT_TIMEOUT) == Err(RecvTimeoutError::Disconnected));
+        assert!(sys.signal_all_acked("missing").is_empty());
+        sys.clear();
+        assert!(ATOMIC.load(Ordering::SeqCst) == 2, "Actual: {:?}", ATOMIC.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_wait_any() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("slow", &ATOMIC, |x: Arc<&AtomicUsize>| {
+            thread::sleep(Duration::from_millis(200));
+            x.fetch_add(1, Ordering::SeqCst);
+        });
+        sys.register_static("fast", &ATOMIC, |x: Arc<&AtomicUsize>| {
+            thread::sleep(Duration::from_millis(20));
+            x.fetch_add(1, Ordering::SeqCst);
+        });
+        sys.register_static("fast", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.signal("slow");
+        sys.signal("fast");
+
+        let first = sys.wait_any(&["slow", "fast"]);
+        assert!(first == "fast", "Actual: {:?}", first);
+        assert!(ATOMIC.load(Ordering::SeqCst) == 2, "Actual: {:?}", ATOMIC.load(Ordering::SeqCst));
+        assert!(sys.wait_any(&["slow"]) == "slow");
+        assert!(ATOMIC.load(Ordering::SeqCst) == 3, "Actual: {:?}", ATOMIC.load(Ordering::SeqCst));
+        assert!(sys.wait_any(&["slow", "fast"]) == "slow");
+        sys.clear();
+    }
+}
@@ -0,0 +1,59 @@
+use core_affinity::CoreId;
+
+use super::EcstaticSystems;
+
+impl EcstaticSystems {
+    /// The core the next worker spawned into a category is pinned to, taking the category's cores in turn by index.
+    pub(crate) fn next_core(&self, category: &str) -> Option<usize> {
+        let cores = &self.configs.get(category)?.cores;
+        if cores.is_empty() {
+            return None;
+        }
+        let index = self.handles.get(category).map_or(0, Vec::len);
+        Some(cores[index % cores.len()])
+    }
+}
+
+/// Pins the calling thread to a core. Does nothing where the platform does not support pinning or the core does not exist.
+pub(crate) fn pin_current(core: usize) {
+    let _ = core_affinity::set_for_current(CoreId { id: core });
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::fs;
+    use std::sync::{Arc, Mutex};
+
+    use crate::{CategoryConfig, EcstaticSystems};
+
+    //the cores the worker thread may run on, as listed by the kernel e.g. "0-3,6".
+    fn allowed_cpus(seen: Arc<&Mutex<Vec<String>>>) {
+        let status = fs::read_to_string("/proc/thread-self/status").unwrap();
+        let allowed = status.lines()
+            .find_map(|line| line.strip_prefix("Cpus_allowed_list:"))
+            .unwrap();
+        seen.lock().unwrap().push(String::from(allowed.trim()));
+    }
+
+    #[test]
+    fn test_pinned_workers() {
+        let cores = match core_affinity::get_core_ids() {
+            Some(ids) if !ids.is_empty() => ids,
+            _ => return,
+        };
+        let mut sys = EcstaticSystems::new();
+        static SEEN: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        let first = cores[0].id;
+        let last = cores[cores.len() - 1].id;
+
+        sys.configure_category("pinned", CategoryConfig { cores: vec![first, last], ..Default::default() });
+        for _ in 0..3 {
+            sys.register_static("pinned", &SEEN, allowed_cpus);
+        }
+        sys.tick_once_sync(&["pinned"]).unwrap();
+        sys.clear();
+
+        let expected: Vec<String> = [first, last, first].iter().map(|core| core.to_string()).collect();
+        assert!(*SEEN.lock().unwrap() == expected, "Actual: {:?}", *SEEN.lock().unwrap());
+    }
+}
@@ -0,0 +1,77 @@
+use std::any::Any;
+use std::marker::Send;
+use std::sync::{Arc, Mutex};
+
+use super::returning::drain;
+use super::{EcstaticSystems, ThreadHandle};
+
+/// Folds the buffered results of a category's handles into its running value.
+type Fold = Box<dyn Fn(&[ThreadHandle]) + Send + Sync>;
+
+pub(crate) struct Aggregator {
+    fold: Fold,
+    acc: Arc<dyn Any + Send + Sync>,
+}
+
+impl EcstaticSystems {
+    /// Combines the results of every returning system of type `R` in a category into one running value,
+    /// starting from `init`. Results are folded in with `reduce` whenever the value is read through `aggregate_value`,
+    /// so they are no longer available to `collect_results`. Replaces any aggregator the category already has.
+    pub fn register_aggregator<Acc: Any + Send, R: Any + Send>(&mut self, category: &str, reduce: fn(Acc, R) -> Acc, init: Acc) {
+        let acc = Arc::new(Mutex::new(Some(init)));
+        let fold_acc = acc.clone();
+        let fold = move |ths: &[ThreadHandle]| {
+            let mut acc = fold_acc.lock().unwrap();
+            let mut value = acc.take().unwrap();
+            for r in ths.iter().flat_map(|th| drain::<R>(&th.results)) {
+                value = reduce(value, r);
+            }
+            *acc = Some(value);
+        };
+        self.aggregators.insert(String::from(category), Aggregator { fold: Box::new(fold), acc });
+    }
+
+    /// Folds every result produced since the last read into a category's aggregate and returns it.
+    /// Returns `None` if the category has no aggregator with a value of type `Acc`.
+    pub fn aggregate_value<Acc: Any + Send + Clone>(&self, category: &str) -> Option<Acc> {
+        let aggregator = self.aggregators.get(category)?;
+        let acc = aggregator.acc.downcast_ref::<Mutex<Option<Acc>>>()?;
+        (aggregator.fold)(self.handles.get(category).map_or(&[], Vec::as_slice));
+        let value = acc.lock().unwrap().clone();
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::test_util::assert_invocations;
+    use crate::EcstaticSystems;
+
+    #[test]
+    fn test_aggregate_sum() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMICS: [AtomicUsize; 3] = [AtomicUsize::new(1), AtomicUsize::new(2), AtomicUsize::new(3)];
+
+        for atomic in ATOMICS.iter() {
+            sys.register_returning("testing", atomic, |x| x.load(Ordering::SeqCst));
+        }
+        sys.register_aggregator("testing", |acc: usize, r: usize| acc + r, 0);
+        assert!(sys.aggregate_value::<usize>("testing") == Some(0));
+        assert!(sys.aggregate_value::<u32>("testing").is_none());
+        assert!(sys.aggregate_value::<usize>("missing").is_none());
+
+        for tick in 1..=3 {
+            sys.signal("testing");
+            for index in 0..3 {
+                assert_invocations(&sys, "testing", index, tick);
+            }
+            let sum = sys.aggregate_value::<usize>("testing");
+            assert!(sum == Some(6 * tick), "Actual: {:?}", sum);
+        }
+        assert!(sys.collect_results::<usize>("testing").iter().all(Vec::is_empty));
+        sys.drop_join_category("testing");
+        assert!(sys.aggregate_value::<usize>("testing").is_none());
+    }
+}
@@ -0,0 +1,72 @@
+use super::EcstaticSystems;
+
+/// How signals sent to an aliased category are routed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AliasMode {
+    /// Signals reach both the aliased category and its target.
+    Mirror,
+    /// Signals only reach the target. The aliased category does not need to exist.
+    Redirect,
+}
+
+impl EcstaticSystems {
+    /// Routes signals sent through `signal` to `from` on to `to` as well, or instead, depending on `mode`.
+    /// Aliases are a single hop and only apply to `signal`; broadcasts such as `signal_all` reach every category once.
+    pub fn alias_category(&mut self, from: &str, to: &str, mode: AliasMode) {
+        self.aliases.insert(String::from(from), (String::from(to), mode));
+    }
+
+    /// Stops routing signals for `from`, returning its previous target.
+    pub fn remove_alias(&mut self, from: &str) -> Option<String> {
+        self.aliases.remove(from).map(|(to, _)| to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::AliasMode;
+    use crate::test_util::assert_invocations;
+    use crate::EcstaticSystems;
+
+    #[test]
+    fn test_alias_mirror() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("old", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("new", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.alias_category("old", "new", AliasMode::Mirror);
+
+        sys.signal("old");
+        assert_invocations(&sys, "old", 0, 1);
+        assert_invocations(&sys, "new", 0, 1);
+        sys.signal_all();
+        assert_invocations(&sys, "old", 0, 2);
+        assert_invocations(&sys, "new", 0, 2);
+
+        assert!(sys.remove_alias("old") == Some(String::from("new")));
+        sys.signal("old");
+        assert_invocations(&sys, "old", 0, 3);
+        assert_invocations(&sys, "new", 0, 2);
+        sys.clear();
+    }
+
+    #[test]
+    fn test_alias_redirect() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("old", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("new", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.alias_category("old", "new", AliasMode::Redirect);
+        sys.alias_category("retired", "new", AliasMode::Redirect);
+
+        sys.signal("old");
+        sys.signal("retired");
+        assert_invocations(&sys, "new", 0, 2);
+        assert_invocations(&sys, "old", 0, 0);
+        sys.clear();
+    }
+}
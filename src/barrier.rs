@@ -0,0 +1,183 @@
+use std::sync::{Arc, Barrier, Mutex};
+
+use super::EcstaticSystems;
+
+/// The two rendezvous of a single `signal_all_barrier`. Only workers wait at `start`,
+/// the caller waits at `end` as well so it returns once every worker has finished.
+struct Tick {
+    start: Barrier,
+    end: Barrier,
+}
+
+#[derive(Default)]
+struct Slot {
+    pending: Option<Arc<Tick>>,
+    closed: bool,
+}
+
+/// The tick a worker joins with the next signal it receives, shared between its handle and its worker thread.
+#[derive(Clone, Default)]
+pub(crate) struct TickSlot(Arc<Mutex<Slot>>);
+
+impl TickSlot {
+    /// Waits at the start of a pending tick, returning a guard which waits at its end once dropped, even while unwinding.
+    pub(crate) fn join(&self) -> Option<TickGuard> {
+        let tick = self.0.lock().unwrap().pending.take()?;
+        tick.start.wait();
+        Some(TickGuard(tick))
+    }
+
+    /// Stops later ticks from counting the worker, and passes straight through a tick it was already counted in.
+    fn close(&self) {
+        let pending = {
+            let mut slot = self.0.lock().unwrap();
+            slot.closed = true;
+            slot.pending.take()
+        };
+        if let Some(tick) = pending {
+            tick.start.wait();
+            tick.end.wait();
+        }
+    }
+}
+
+pub(crate) struct TickGuard(Arc<Tick>);
+
+impl Drop for TickGuard {
+    fn drop(&mut self) {
+        self.0.end.wait();
+    }
+}
+
+/// Closes a worker's `TickSlot` when its thread stops, however it stops.
+pub(crate) struct CloseOnExit(pub(crate) TickSlot);
+
+impl Drop for CloseOnExit {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+impl EcstaticSystems {
+    /// Signals every live worker once and blocks until all of them have finished. No worker starts running
+    /// until every worker has received its signal, so systems can work on one buffer while the caller swaps
+    /// another once this returns. Returns how many workers took part.
+    ///
+    /// Workers whose thread has exited are skipped, and a worker which panics still releases the others.
+    /// Handles without a signaled thread of their own, such as inline systems, receivers and pools, are skipped.
+    /// A worker with signals already buffered runs the first of them inside the barrier and this call's signal afterwards.
+    pub fn signal_all_barrier(&self) -> usize {
+        if self.is_frozen() {
+            return 0;
+        }
+        //a worker counted in two ticks at once could join them in a different order than another worker.
+        let _serial = self.tick_lock.lock().unwrap();
+        let targets: Vec<_> = self.order.iter()
+            .flat_map(|category| self.handles[category].iter().enumerate().map(move |(index, th)| (category, index, th)))
+            .filter(|(_, _, th)| th.sx.is_some())
+            .filter_map(|(category, index, th)| Some((category, index, th, th.ticks.as_ref()?)))
+            .collect();
+        //every slot stays locked until the tick is sized, so no worker can close after being counted without taking it.
+        let mut slots: Vec<_> = targets.iter()
+            .map(|(_, _, _, ticks)| ticks.0.lock().unwrap())
+            .collect();
+        let live = slots.iter().filter(|slot| !slot.closed).count();
+        if live == 0 {
+            return 0;
+        }
+        let tick = Arc::new(Tick { start: Barrier::new(live), end: Barrier::new(live + 1) });
+        for slot in slots.iter_mut().filter(|slot| !slot.closed) {
+            slot.pending = Some(tick.clone());
+        }
+        let live_targets: Vec<_> = targets.iter()
+            .zip(slots.iter())
+            .filter(|(_, slot)| !slot.closed)
+            .map(|((category, index, th, _), _)| (*category, *index, *th))
+            .collect();
+        drop(slots);
+        //a worker which disconnects before receiving its signal passes through the tick when it closes.
+        for (category, index, th) in live_targets {
+            self.signal_handle(category, index, th);
+        }
+        tick.end.wait();
+        live
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crate::test_util::pump_until;
+    use crate::{EcstaticSystems, ExitReason, HandleStatus};
+
+    struct Timed {
+        sleep: Duration,
+        started: AtomicUsize,
+        runs: Mutex<Vec<(Instant, Instant)>>,
+    }
+
+    impl Timed {
+        const fn new(millis: u64) -> Timed {
+            Timed { sleep: Duration::from_millis(millis), started: AtomicUsize::new(0), runs: Mutex::new(Vec::new()) }
+        }
+    }
+
+    fn timed(x: Arc<&Timed>) {
+        let start = Instant::now();
+        x.started.fetch_add(1, Ordering::SeqCst);
+        thread::sleep(x.sleep);
+        x.runs.lock().unwrap().push((start, Instant::now()));
+    }
+
+    #[test]
+    fn test_signal_all_barrier() {
+        let mut sys = EcstaticSystems::new();
+        static FAST: Timed = Timed::new(50);
+        static MEDIUM: Timed = Timed::new(80);
+        static SLOW: Timed = Timed::new(150);
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("fast", &FAST, timed);
+        sys.register_static("medium", &MEDIUM, timed);
+        sys.register_static("slow", &SLOW, timed);
+        sys.register_inline("inline", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.signal("slow");
+        pump_until(&sys, |_| SLOW.started.load(Ordering::SeqCst) == 1);
+
+        assert!(sys.signal_all_barrier() == 3);
+        let returned = Instant::now();
+        let ticks: Vec<(Instant, Instant)> = [&FAST, &MEDIUM, &SLOW].iter()
+            .map(|timed| *timed.runs.lock().unwrap().last().unwrap())
+            .collect();
+        let last_start = ticks.iter().map(|(start, _)| *start).max().unwrap();
+        let first_end = ticks.iter().map(|(_, end)| *end).min().unwrap();
+        assert!(last_start <= first_end, "Actual: {:?}", ticks);
+        assert!(ticks.iter().all(|(_, end)| *end <= returned), "Actual: {:?}", ticks);
+        assert!(SLOW.runs.lock().unwrap()[0].1 <= last_start);
+        assert!(ATOMIC.load(Ordering::SeqCst) == 0);
+        sys.clear();
+        assert!(SLOW.runs.lock().unwrap().len() == 2);
+    }
+
+    #[test]
+    fn test_signal_all_barrier_panicked() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("ok", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("panics", &ATOMIC, |_| panic!("tick panicked"));
+        assert!(sys.signal_all_barrier() == 2);
+        assert!(ATOMIC.load(Ordering::SeqCst) == 1);
+        pump_until(&sys, |sys| sys.handle_status("panics", 0) == Ok(HandleStatus::Exited));
+        assert!(sys.exit_reason("panics", 0) == Some(ExitReason::Panicked));
+
+        assert!(sys.signal_all_barrier() == 1);
+        assert!(ATOMIC.load(Ordering::SeqCst) == 2);
+        sys.clear();
+        assert!(sys.signal_all_barrier() == 0);
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+
+use super::watchdog::signal_own;
+use super::{EcstaticError, EcstaticSystems};
+
+impl EcstaticSystems {
+    /// Signals every thread handle without blocking, skipping handles whose buffer is full.
+    /// Each call starts from the category after the one the previous call started from, so under
+    /// sustained backpressure no category is always the last to be offered a signal.
+    /// Returns how many handles were signaled.
+    pub fn signal_all_nonblocking(&self) -> usize {
+        if self.order.is_empty() || self.is_frozen() {
+            return 0;
+        }
+        let start = self.cursor.fetch_add(1, Ordering::Relaxed) % self.order.len();
+        self.order[start..].iter()
+            .chain(self.order[..start].iter())
+            .flat_map(|category| self.handles[category].iter())
+            .filter(|th| th.try_send_signal())
+            .count()
+    }
+
+    /// Signals every handle in one category exactly once, blocking while a handle's buffer is full.
+    /// Returns how many handles were signaled without waiting for them to run. A system which signals its own
+    /// full handle gets `EcstaticError::Reentrancy` rather than blocking forever, and handles after it are not signaled.
+    /// Once `begin_shutdown` has been called this returns `EcstaticError::ShuttingDown`, including for a call which
+    /// was blocked on a full buffer when the shutdown began.
+    pub fn signal_round(&self, category: &str) -> Result<usize, EcstaticError> {
+        if self.is_shutting_down() {
+            return Err(EcstaticError::ShuttingDown);
+        }
+        let ths = self.handles.get(category).ok_or_else(|| EcstaticError::UnknownCategory(String::from(category)))?;
+        if self.is_frozen() {
+            return Ok(0);
+        }
+        let mut signaled = 0;
+        for (index, th) in ths.iter().enumerate() {
+            let sent = if th.is_own_thread() {
+                match signal_own(category, index, th) {
+                    Err(e @ EcstaticError::Reentrancy { .. }) => return Err(e),
+                    result => result.ok(),
+                }
+            } else {
+                self.signal_handle(category, index, th)
+            };
+            if sent.is_some() {
+                signaled += 1;
+            }
+        }
+        if self.is_shutting_down() {
+            return Err(EcstaticError::ShuttingDown);
+        }
+        Ok(signaled)
+    }
+
+    /// Signals a category like `signal`, but only if `cond` returns true when called, so workers are not woken
+    /// just to find there is nothing to do. `cond` runs on the calling thread. Returns whether the category was signaled.
+    pub fn signal_when<F: Fn() -> bool>(&self, category: &str, cond: F) -> bool {
+        if !cond() {
+            return false;
+        }
+        self.signal(category);
+        true
+    }
+
+    /// Signals each category in `counts` the given number of times like `signal`, in name order.
+    /// Categories which are not registered are skipped and reported together once the rest have been signaled.
+    pub fn signal_counts(&self, counts: &HashMap<&str, usize>) -> Result<(), Vec<EcstaticError>> {
+        let mut categories: Vec<(&str, usize)> = counts.iter().map(|(category, count)| (*category, *count)).collect();
+        categories.sort_unstable();
+        let mut errors = Vec::new();
+        for (category, count) in categories {
+            if !self.handles.contains_key(category) && !self.aliases.contains_key(category) {
+                errors.push(EcstaticError::UnknownCategory(String::from(category)));
+                continue;
+            }
+            for _ in 0..count {
+                self.signal(category);
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::test_util::assert_invocations;
+    use crate::{CategoryConfig, EcstaticError, EcstaticSystems};
+
+    #[test]
+    fn test_signal_all_nonblocking_no_starvation() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        for (category, buffer_size) in [("tiny", 1), ("small", 2), ("large", 60)].iter() {
+            sys.configure_category(category, CategoryConfig { buffer_size: Some(*buffer_size), ..Default::default() });
+            sys.register_static(category, &ATOMIC, |_| thread::sleep(Duration::from_millis(2)));
+        }
+        for _ in 0..100 {
+            sys.signal_all_nonblocking();
+        }
+        sys.step(Duration::from_secs(5));
+
+        for category in ["tiny", "small", "large"].iter() {
+            let stats = sys.stats(category, 0).unwrap();
+            assert!(stats.count > 1, "{} only ran {} times", category, stats.count);
+        }
+        assert!(sys.signal_all_nonblocking() == 3);
+        sys.clear();
+    }
+
+    #[test]
+    fn test_signal_round() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        for _ in 0..3 {
+            sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        }
+        sys.register_static("other", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        assert!(sys.signal_round("testing") == Ok(3));
+        assert!(sys.signal_round("missing") == Err(EcstaticError::UnknownCategory(String::from("missing"))));
+        for index in 0..3 {
+            assert_invocations(&sys, "testing", index, 1);
+        }
+        sys.clear();
+
+        assert!(ATOMIC.load(Ordering::Relaxed) == 3, "Actual: {:?}", ATOMIC.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_signal_when() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        let dirty = AtomicBool::new(false);
+
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        assert!(!sys.signal_when("testing", || dirty.load(Ordering::SeqCst)));
+        dirty.store(true, Ordering::SeqCst);
+        assert!(sys.signal_when("testing", || dirty.load(Ordering::SeqCst)));
+        assert!(sys.signal_when("testing", || dirty.load(Ordering::SeqCst)));
+        dirty.store(false, Ordering::SeqCst);
+        assert!(!sys.signal_when("testing", || dirty.load(Ordering::SeqCst)));
+
+        assert_invocations(&sys, "testing", 0, 2);
+        sys.clear();
+        assert!(ATOMIC.load(Ordering::SeqCst) == 2);
+    }
+
+    #[test]
+    fn test_signal_counts() {
+        let mut sys = EcstaticSystems::new();
+        static A: AtomicUsize = AtomicUsize::new(0);
+        static B: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("a", &A, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("b", &B, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        let counts: HashMap<&str, usize> = [("a", 2), ("b", 3)].iter().copied().collect();
+        sys.signal_counts(&counts).unwrap();
+        assert_invocations(&sys, "a", 0, 2);
+        assert_invocations(&sys, "b", 0, 3);
+
+        let counts: HashMap<&str, usize> = [("missing", 1), ("a", 1), ("gone", 2)].iter().copied().collect();
+        let result = sys.signal_counts(&counts);
+        assert!(result == Err(vec![
+            EcstaticError::UnknownCategory(String::from("gone")),
+            EcstaticError::UnknownCategory(String::from("missing")),
+        ]), "Actual: {:?}", result);
+        assert_invocations(&sys, "a", 0, 3);
+        sys.clear();
+        assert!(A.load(Ordering::SeqCst) == 3 && B.load(Ordering::SeqCst) == 3);
+    }
+}
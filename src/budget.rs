@@ -0,0 +1,77 @@
+use std::any::Any;
+use std::marker::{Send, Sync};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::{data_slot, EcstaticSystems};
+
+/// The point in time a budgeted system should return by, see `register_budgeted`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Deadline(Instant);
+
+impl Deadline {
+    pub fn instant(&self) -> Instant {
+        self.0
+    }
+
+    pub fn expired(&self) -> bool {
+        Instant::now() >= self.0
+    }
+
+    /// Time left before the deadline, or zero once it has passed.
+    pub fn remaining(&self) -> Duration {
+        self.0.saturating_duration_since(Instant::now())
+    }
+}
+
+impl EcstaticSystems {
+    /// Registers a system like `register_static` for incremental work. Every invocation receives a `Deadline`
+    /// `budget` after it started, and is expected to return once it expires. The deadline is not enforced.
+    pub fn register_budgeted<'a: 'static, T: Any + Send + Sync>(&mut self, category: &str, data: &'a T, f: fn(Arc<&'a T>, Deadline), budget: Duration) {
+        if self.is_shutting_down() {
+            return;
+        }
+        let data = data_slot(data);
+        let worker_data = data.clone();
+        let mut th = self.system_create(category, move || f(worker_data.load_full(), Deadline(Instant::now() + budget)));
+        th.data = Some(data);
+        self.push_handle(category, th);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::Deadline;
+    use crate::test_util::assert_invocations;
+    use crate::EcstaticSystems;
+
+    fn incremental(work: Arc<&AtomicUsize>, deadline: Deadline) {
+        while !deadline.expired() {
+            work.fetch_add(1, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(1).min(deadline.remaining()));
+        }
+    }
+
+    #[test]
+    fn test_budgeted_stops_at_deadline() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        let budget = Duration::from_millis(30);
+
+        sys.register_budgeted("testing", &ATOMIC, incremental, budget);
+        sys.signal("testing");
+        sys.signal("testing");
+        assert_invocations(&sys, "testing", 0, 2);
+
+        let stats = sys.stats("testing", 0).unwrap();
+        assert!(stats.min_run_time >= budget, "Actual: {:?}", stats);
+        assert!(stats.max_run_time < budget * 4, "Actual: {:?}", stats);
+        assert!(ATOMIC.load(Ordering::SeqCst) > 2);
+        sys.drop_join_category("testing");
+    }
+}
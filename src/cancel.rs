@@ -0,0 +1,92 @@
+use std::any::Any;
+use std::marker::{Send, Sync};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use super::{data_slot, EcstaticSystems};
+
+/// A flag handed to long-running systems which is tripped when their category is being torn down.
+/// Systems should check it periodically and return early once it is cancelled.
+#[derive(Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> CancelToken {
+        CancelToken::default()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+}
+
+impl EcstaticSystems {
+    /// Registers a system like `register_static`, but also passes it a `CancelToken` which is tripped
+    /// before its sender is dropped. Signals still buffered once the token is tripped are skipped.
+    pub fn register_cancellable<'a: 'static, T: Any + Send + Sync>(&mut self, category: &str, data: &'a T, f: fn(Arc<&'a T>, CancelToken)) {
+        if self.is_shutting_down() {
+            return;
+        }
+        let token = CancelToken::new();
+        let worker_token = token.clone();
+        let data = data_slot(data);
+        let worker_data = data.clone();
+        let mut th = self.system_create(category, move || {
+            if !worker_token.is_cancelled() {
+                f(worker_data.load_full(), worker_token.clone());
+            }
+        });
+        th.cancel = Some(token);
+        th.data = Some(data);
+        self.push_handle(category, th);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use super::CancelToken;
+    use crate::test_util::pump_until;
+    use crate::EcstaticSystems;
+
+    struct LongTask {
+        started: AtomicBool,
+        iterations: AtomicUsize,
+    }
+
+    fn long_task(task: Arc<&LongTask>, token: CancelToken) {
+        task.started.store(true, Ordering::SeqCst);
+        for _ in 0..10_000 {
+            if token.is_cancelled() {
+                return;
+            }
+            task.iterations.fetch_add(1, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_cancellable_exits_promptly() {
+        let mut sys = EcstaticSystems::new();
+        static TASK: LongTask = LongTask { started: AtomicBool::new(false), iterations: AtomicUsize::new(0) };
+
+        sys.register_cancellable("testing", &TASK, long_task);
+        sys.signal("testing");
+        sys.signal("testing");
+        pump_until(&sys, |_| TASK.started.load(Ordering::SeqCst));
+
+        let start = Instant::now();
+        sys.drop_join_category("testing");
+
+        assert!(start.elapsed() < Duration::from_secs(2), "Took: {:?}", start.elapsed());
+        assert!(TASK.iterations.load(Ordering::SeqCst) < 10_000);
+    }
+}
@@ -0,0 +1,119 @@
+use std::sync::mpsc::{self, Receiver, SendError, Sender, SyncSender, TrySendError};
+
+use super::EcstaticSystems;
+
+/// The kind of channel a category's signaled workers receive signals through, see `CategoryConfig::channel`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelKind {
+    /// Buffers this many signals, after which `signal` blocks until the worker catches up.
+    Bounded(usize),
+    /// Buffers any number of signals, so `signal` never blocks. A worker which keeps falling behind
+    /// grows its buffer without limit, so producers need some other way to notice, e.g. `on_slow_consumer`.
+    Unbounded,
+}
+
+/// The sending half of a worker's signal channel.
+#[derive(Clone, Debug)]
+pub enum SignalSender {
+    Bounded(SyncSender<()>),
+    Unbounded(Sender<()>),
+}
+
+impl SignalSender {
+    /// Sends a signal, blocking while a bounded buffer is full.
+    pub fn send(&self, signal: ()) -> Result<(), SendError<()>> {
+        match self {
+            SignalSender::Bounded(sx) => sx.send(signal),
+            SignalSender::Unbounded(sx) => sx.send(signal),
+        }
+    }
+
+    /// Sends a signal unless a bounded buffer is full. Unbounded senders are never full.
+    pub fn try_send(&self, signal: ()) -> Result<(), TrySendError<()>> {
+        match self {
+            SignalSender::Bounded(sx) => sx.try_send(signal),
+            SignalSender::Unbounded(sx) => sx.send(signal).map_err(|SendError(signal)| TrySendError::Disconnected(signal)),
+        }
+    }
+}
+
+/// Creates a signal channel, returning it with how many signals it buffers.
+pub(crate) fn signal_channel(kind: ChannelKind) -> (SignalSender, Receiver<()>, usize) {
+    match kind {
+        ChannelKind::Bounded(capacity) => {
+            let (sx, rx) = mpsc::sync_channel(capacity);
+            (SignalSender::Bounded(sx), rx, capacity)
+        }
+        ChannelKind::Unbounded => {
+            let (sx, rx) = mpsc::channel();
+            (SignalSender::Unbounded(sx), rx, usize::MAX)
+        }
+    }
+}
+
+impl EcstaticSystems {
+    pub(crate) fn channel_kind(&self, category: &str) -> ChannelKind {
+        match self.configs.get(category).and_then(|config| config.channel) {
+            Some(kind) => kind,
+            None => ChannelKind::Bounded(self.buffer_size(category)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    use super::ChannelKind;
+    use crate::test_util::{assert_invocations, gated, pump_until, Gated};
+    use crate::{CategoryConfig, EcstaticSystems};
+
+    #[test]
+    fn test_unbounded_never_blocks() {
+        let mut sys = EcstaticSystems::new();
+        static GATED: Gated = Gated::new();
+
+        sys.configure_category("testing", CategoryConfig { channel: Some(ChannelKind::Unbounded), ..Default::default() });
+        sys.register_static("testing", &GATED, gated);
+        sys.signal("testing");
+        pump_until(&sys, |_| GATED.runs.load(Ordering::SeqCst) == 1);
+        for _ in 0..1000 {
+            sys.signal("testing");
+        }
+        assert!(sys.buffer_capacity("testing", 0) == Ok(usize::MAX));
+        assert!(sys.saturated_categories().is_empty());
+
+        GATED.released.store(true, Ordering::SeqCst);
+        assert_invocations(&sys, "testing", 0, 1001);
+        sys.clear();
+    }
+
+    #[test]
+    fn test_bounded_blocks() {
+        let mut sys = EcstaticSystems::new();
+        static GATED: Gated = Gated::new();
+        static SENT: AtomicBool = AtomicBool::new(false);
+
+        sys.configure_category("testing", CategoryConfig { channel: Some(ChannelKind::Bounded(1)), ..Default::default() });
+        sys.register_static("testing", &GATED, gated);
+        sys.signal("testing");
+        pump_until(&sys, |_| GATED.runs.load(Ordering::SeqCst) == 1);
+        sys.signal("testing");
+        assert!(sys.buffer_capacity("testing", 0) == Ok(1));
+
+        thread::scope(|s| {
+            s.spawn(|| {
+                sys.signal("testing");
+                SENT.store(true, Ordering::SeqCst);
+            });
+            thread::sleep(Duration::from_millis(50));
+            assert!(!SENT.load(Ordering::SeqCst));
+            GATED.released.store(true, Ordering::SeqCst);
+        });
+        assert!(SENT.load(Ordering::SeqCst));
+        assert_invocations(&sys, "testing", 0, 3);
+        sys.clear();
+    }
+}
@@ -0,0 +1,64 @@
+use super::EcstaticSystems;
+
+/// A coarse scheduling class for a category, used to signal groups of categories together.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SchedulerClass {
+    Realtime,
+    #[default]
+    Normal,
+    Background,
+}
+
+impl EcstaticSystems {
+    /// Assigns a category to a scheduler class. Categories are `SchedulerClass::Normal` until assigned.
+    pub fn set_class(&mut self, category: &str, class: SchedulerClass) {
+        self.configs.entry(String::from(category)).or_default().class = class;
+    }
+
+    /// The scheduler class a category is assigned to.
+    pub fn class(&self, category: &str) -> SchedulerClass {
+        self.configs.get(category).map(|config| config.class).unwrap_or_default()
+    }
+
+    /// Sends a signal to every thread handle in every category assigned to `class`.
+    pub fn signal_class(&self, class: SchedulerClass) {
+        if self.is_frozen() {
+            return;
+        }
+        for category in self.order.iter().filter(|category| self.class(category) == class) {
+            self.signal_direct(category);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::SchedulerClass;
+    use crate::test_util::assert_invocations;
+    use crate::EcstaticSystems;
+
+    #[test]
+    fn test_signal_class() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("input", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("audio", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("logging", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("ai", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.set_class("input", SchedulerClass::Realtime);
+        sys.set_class("audio", SchedulerClass::Realtime);
+        sys.set_class("logging", SchedulerClass::Background);
+        assert!(sys.class("ai") == SchedulerClass::Normal);
+
+        sys.signal_class(SchedulerClass::Realtime);
+        assert_invocations(&sys, "input", 0, 1);
+        assert_invocations(&sys, "audio", 0, 1);
+        assert_invocations(&sys, "logging", 0, 0);
+        assert_invocations(&sys, "ai", 0, 0);
+        sys.clear();
+        assert!(ATOMIC.load(Ordering::Relaxed) == 2);
+    }
+}
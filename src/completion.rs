@@ -0,0 +1,131 @@
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll, Waker};
+
+use futures_core::Stream;
+
+use super::EcstaticSystems;
+
+#[derive(Default)]
+struct Queue {
+    completed: VecDeque<usize>,
+    waker: Option<Waker>,
+    //handles whose state still holds a listener.
+    live: usize,
+}
+
+impl Queue {
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Forwards the completions of one handle to a `CompletionStream`. Dropped along with the handle's state,
+/// which happens once its category is joined.
+pub(crate) struct Listener {
+    index: usize,
+    queue: Weak<Mutex<Queue>>,
+}
+
+impl Listener {
+    /// Reports a completed invocation, returning false once the stream has been dropped.
+    pub(crate) fn completed(&self) -> bool {
+        match self.queue.upgrade() {
+            Some(queue) => {
+                let mut queue = queue.lock().unwrap();
+                queue.completed.push_back(self.index);
+                queue.wake();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        if let Some(queue) = self.queue.upgrade() {
+            let mut queue = queue.lock().unwrap();
+            queue.live -= 1;
+            queue.wake();
+        }
+    }
+}
+
+/// Yields the index of a handle every time it completes an invocation, see `completion_stream`.
+pub struct CompletionStream {
+    queue: Arc<Mutex<Queue>>,
+}
+
+impl Stream for CompletionStream {
+    type Item = (usize, ());
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<(usize, ())>> {
+        let mut queue = self.queue.lock().unwrap();
+        match queue.completed.pop_front() {
+            Some(index) => Poll::Ready(Some((index, ()))),
+            None if queue.live == 0 => Poll::Ready(None),
+            None => {
+                queue.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl EcstaticSystems {
+    /// A stream which yields `(index, ())` every time a handle in the category completes an invocation from now on.
+    /// Handles registered after the stream was created are not included. The stream ends once every included handle
+    /// has been joined, and straight away for a category which is not registered.
+    pub fn completion_stream(&self, category: &str) -> CompletionStream {
+        let queue = Arc::new(Mutex::new(Queue::default()));
+        for (index, th) in self.handles.get(category).into_iter().flatten().enumerate() {
+            queue.lock().unwrap().live += 1;
+            th.state.listen(Listener { index, queue: Arc::downgrade(&queue) });
+        }
+        CompletionStream { queue }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures_core::Stream;
+
+    use super::CompletionStream;
+    use crate::EcstaticSystems;
+
+    async fn next(stream: &mut CompletionStream) -> Option<(usize, ())> {
+        future::poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await
+    }
+
+    #[tokio::test]
+    async fn test_completion_stream() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        let mut stream = sys.completion_stream("testing");
+        for _ in 0..3 {
+            sys.signal("testing");
+        }
+
+        let mut completed = [0; 2];
+        for _ in 0..6 {
+            let (index, ()) = next(&mut stream).await.unwrap();
+            completed[index] += 1;
+        }
+        assert!(completed == [3, 3], "Actual: {:?}", completed);
+        assert!(ATOMIC.load(Ordering::SeqCst) == 6);
+        sys.clear();
+        assert!(next(&mut stream).await.is_none());
+        assert!(next(&mut sys.completion_stream("missing")).await.is_none());
+    }
+}
@@ -0,0 +1,45 @@
+use std::hash::Hash;
+use std::marker::{Send, Sync};
+use std::sync::Arc;
+
+use dashmap::DashMap;
+
+use super::EcstaticSystems;
+
+impl EcstaticSystems {
+    /// Registers a static system over a concurrent component map, so that several systems can read and write
+    /// components at the same time without a global lock.
+    ///
+    /// The map only locks the shard holding a key, which has two hazards systems need to respect:
+    /// holding a reference from `get`/`get_mut` while touching another key can deadlock if both keys share a shard,
+    /// and there is no ordering between systems, so systems writing the same key see each other's writes in any order.
+    /// Keep references short lived and give each system its own keys where ordering matters.
+    pub fn register_static_component<'a: 'static, K: Eq + Hash + Send + Sync, V: Send + Sync>(&mut self, category: &str, map: &'a DashMap<K, V>, f: fn(Arc<&'a DashMap<K, V>>)) {
+        self.register_static(category, map, f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use dashmap::DashMap;
+
+    use crate::EcstaticSystems;
+
+    #[test]
+    fn test_component_systems_disjoint_keys() {
+        let mut sys = EcstaticSystems::new();
+        let map: &'static DashMap<&str, usize> = Box::leak(Box::new(DashMap::new()));
+
+        sys.register_static_component("physics", map, |m| { *m.entry("position").or_insert(0) += 1; });
+        sys.register_static_component("physics", map, |m| { *m.entry("velocity").or_insert(0) += 2; });
+        for _ in 0..100 {
+            sys.step(Duration::from_secs(5));
+        }
+        sys.drop_join_category("physics");
+
+        assert!(*map.get("position").unwrap() == 100);
+        assert!(*map.get("velocity").unwrap() == 200);
+    }
+}
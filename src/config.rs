@@ -0,0 +1,155 @@
+use super::{ChannelKind, EcstaticError, EcstaticSystems, PanicPolicy, SchedulerClass};
+
+/// How many signals a worker buffers when its category does not configure a buffer size.
+pub const DEFAULT_BUFFER_SIZE: usize = 60;
+
+/// Settings applied to every worker spawned into a category after it is configured.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CategoryConfig {
+    /// The stack size in bytes of each worker thread. Uses the platform default when `None`.
+    pub stack_size: Option<usize>,
+    /// How many signals each worker buffers before `signal` blocks. Defaults to `DEFAULT_BUFFER_SIZE`.
+    pub buffer_size: Option<usize>,
+    /// The channel signaled workers receive signals through, which takes precedence over `buffer_size`.
+    /// Defaults to `ChannelKind::Bounded(buffer_size)`. Receivers, responders and worker pools are always bounded,
+    /// by the size of a `ChannelKind::Bounded` channel if one is set.
+    pub channel: Option<ChannelKind>,
+    /// The scheduler class the category belongs to, see `signal_class`.
+    pub class: SchedulerClass,
+    /// Categories with a higher priority are joined first by `clear`, see `set_teardown_priority`.
+    pub teardown_priority: i32,
+    /// What a worker does after its system panics, see `PanicPolicy`.
+    pub panic_policy: PanicPolicy,
+    /// Core ids the category's workers are pinned to, handed out in turn by each worker's index in the category.
+    /// Workers are not pinned when empty, or where the platform does not support pinning.
+    #[cfg(feature = "core_affinity")]
+    pub cores: Vec<usize>,
+}
+
+impl EcstaticSystems {
+    /// Sets the configuration used when spawning workers into a category.
+    /// Only fields which are `Some` or differ from their default are applied, so settings made earlier through
+    /// `set_class`, `set_teardown_priority` or `set_panic_policy` are kept unless `config` sets them again.
+    /// Workers which are already running keep the configuration they were spawned with.
+    pub fn configure_category(&mut self, category: &str, config: CategoryConfig) {
+        let defaults = CategoryConfig::default();
+        let current = self.configs.entry(String::from(category)).or_default();
+        if config.stack_size.is_some() {
+            current.stack_size = config.stack_size;
+        }
+        if config.buffer_size.is_some() {
+            current.buffer_size = config.buffer_size;
+        }
+        if config.channel.is_some() {
+            current.channel = config.channel;
+        }
+        if config.class != defaults.class {
+            current.class = config.class;
+        }
+        if config.teardown_priority != defaults.teardown_priority {
+            current.teardown_priority = config.teardown_priority;
+        }
+        if config.panic_policy != defaults.panic_policy {
+            current.panic_policy = config.panic_policy;
+        }
+        #[cfg(feature = "core_affinity")]
+        if !config.cores.is_empty() {
+            current.cores = config.cores;
+        }
+    }
+
+    /// Gives `to` the same configuration as `from`, replacing any it had, so systems registered into `to`
+    /// from now on are spawned like those in `from`. Handles are not copied. Fails if `from` is neither configured nor registered.
+    pub fn clone_category_config(&mut self, from: &str, to: &str) -> Result<(), EcstaticError> {
+        match self.configs.get(from).cloned() {
+            Some(config) => {
+                self.configs.insert(String::from(to), config);
+            }
+            None if self.has_category(from) => {
+                self.configs.remove(to);
+            }
+            None => return Err(EcstaticError::UnknownCategory(String::from(from))),
+        }
+        Ok(())
+    }
+
+    pub(crate) fn buffer_size(&self, category: &str) -> usize {
+        match self.configs.get(category) {
+            Some(CategoryConfig { channel: Some(ChannelKind::Bounded(capacity)), .. }) => *capacity,
+            config => config.and_then(|config| config.buffer_size).unwrap_or(DEFAULT_BUFFER_SIZE),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hint;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::CategoryConfig;
+    use crate::test_util::pump_until;
+    use crate::{EcstaticError, EcstaticSystems, HandleStatus, PanicPolicy, SchedulerClass};
+
+    //each frame holds a 4KiB buffer, so 4096 frames need well over the default 2MiB stack.
+    const DEPTH: usize = 4096;
+
+    fn recurse(depth: usize) -> usize {
+        let buf = hint::black_box([1u8; 4096]);
+        if depth == 0 {
+            0
+        } else {
+            recurse(depth - 1) + buf[depth % buf.len()] as usize
+        }
+    }
+
+    #[test]
+    fn test_configured_stack_size() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.configure_category("testing", CategoryConfig { stack_size: Some(256 * 1024 * 1024), ..Default::default() });
+        sys.register_static("testing", &ATOMIC, |x|{ x.store(recurse(DEPTH), Ordering::SeqCst); });
+        sys.signal("testing");
+        sys.drop_join_category("testing");
+
+        assert!(ATOMIC.load(Ordering::Relaxed) == DEPTH);
+    }
+
+    #[test]
+    fn test_clone_category_config() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.configure_category("template", CategoryConfig { buffer_size: Some(3), class: SchedulerClass::Background, ..Default::default() });
+        sys.set_panic_policy("template", PanicPolicy::Disable);
+        sys.configure_category("copy", CategoryConfig { buffer_size: Some(8), ..Default::default() });
+        sys.clone_category_config("template", "copy").unwrap();
+        assert!(sys.clone_category_config("missing", "copy") == Err(EcstaticError::UnknownCategory(String::from("missing"))));
+
+        sys.register_static("copy", &ATOMIC, |_| panic!("copied policy"));
+        assert!(sys.buffer_capacity("copy", 0) == Ok(3));
+        assert!(sys.class("copy") == SchedulerClass::Background);
+        sys.signal("copy");
+        pump_until(&sys, |sys| sys.handle_status("copy", 0) == Ok(HandleStatus::Disabled));
+        assert!(!sys.has_category("template"));
+        sys.clear();
+    }
+
+    #[test]
+    fn test_configure_category_keeps_earlier_settings() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.set_class("testing", SchedulerClass::Background);
+        sys.set_panic_policy("testing", PanicPolicy::Disable);
+        sys.configure_category("testing", CategoryConfig { buffer_size: Some(3), ..Default::default() });
+        sys.configure_category("testing", CategoryConfig { stack_size: Some(1024 * 1024), ..Default::default() });
+
+        sys.register_static("testing", &ATOMIC, |_| panic!("kept policy"));
+        assert!(sys.buffer_capacity("testing", 0) == Ok(3));
+        assert!(sys.class("testing") == SchedulerClass::Background);
+        sys.signal("testing");
+        pump_until(&sys, |sys| sys.handle_status("testing", 0) == Ok(HandleStatus::Disabled));
+        sys.clear();
+    }
+}
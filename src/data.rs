@@ -0,0 +1,77 @@
+use std::any::Any;
+use std::marker::{Send, Sync};
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+
+use super::{EcstaticError, EcstaticSystems};
+
+impl EcstaticSystems {
+    /// Swaps the data a running system operates on. The system sees `new` from its next invocation onwards,
+    /// while an invocation already in progress finishes with the old data.
+    pub fn replace_data<T: Any + Send + Sync>(&self, category: &str, index: usize, new: &'static T) -> Result<(), EcstaticError> {
+        let th = self.handle(category, index)?;
+        let data = th.data.as_ref()
+            .and_then(|data| data.downcast_ref::<ArcSwap<&'static T>>())
+            .ok_or_else(|| EcstaticError::TypeMismatch { category: String::from(category), index })?;
+        data.store(Arc::new(new));
+        Ok(())
+    }
+
+    /// Calls `f` with the data a handle's system currently operates on, the same data its next invocation will see.
+    /// Returns `None` if the handle does not exist or its data is not a `T`.
+    pub fn with_data<T: Any + Send + Sync, R, F: FnOnce(&T) -> R>(&self, category: &str, index: usize, f: F) -> Option<R> {
+        let data = self.handle(category, index).ok()?
+            .data.as_ref()?
+            .downcast_ref::<ArcSwap<&'static T>>()?
+            .load_full();
+        Some(f(*data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use crate::test_util::assert_invocations;
+    use crate::{EcstaticError, EcstaticSystems};
+
+    #[test]
+    fn test_replace_data() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        static ATOMIC1: AtomicUsize = AtomicUsize::new(10);
+        static WRONG: AtomicU32 = AtomicU32::new(0);
+
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.step(Duration::from_secs(5));
+        assert!(sys.replace_data("testing", 0, &ATOMIC1) == Ok(()));
+        sys.step(Duration::from_secs(5));
+        sys.step(Duration::from_secs(5));
+
+        assert!(sys.replace_data("testing", 0, &WRONG) == Err(EcstaticError::TypeMismatch { category: String::from("testing"), index: 0 }));
+        sys.drop_join_category("testing");
+        assert!(ATOMIC.load(Ordering::Relaxed) == 1);
+        assert!(ATOMIC1.load(Ordering::Relaxed) == 12);
+    }
+
+    #[test]
+    fn test_with_data() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        static SEEN: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("testing", &ATOMIC, |x|{ SEEN.store(x.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst); });
+        for _ in 0..3 {
+            sys.signal("testing");
+        }
+        assert_invocations(&sys, "testing", 0, 3);
+
+        let value = sys.with_data("testing", 0, |x: &AtomicUsize| x.load(Ordering::SeqCst));
+        assert!(value == Some(SEEN.load(Ordering::SeqCst)) && value == Some(3), "Actual: {:?}", value);
+        assert!(sys.with_data("testing", 0, |x: &AtomicU32| x.load(Ordering::SeqCst)).is_none());
+        assert!(sys.with_data("testing", 1, |x: &AtomicUsize| x.load(Ordering::SeqCst)).is_none());
+        sys.drop_join_category("testing");
+    }
+}
@@ -0,0 +1,55 @@
+use std::error::Error;
+use std::fmt;
+
+/// Errors returned by the fallible operations of `EcstaticSystems`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EcstaticError {
+    /// No category with this name has been registered.
+    UnknownCategory(String),
+    /// A category with this name is already registered.
+    CategoryExists(String),
+    /// The category exists but has no handle at this index.
+    UnknownHandle { category: String, index: usize },
+    /// The handle exists but was registered with different types than the ones requested.
+    TypeMismatch { category: String, index: usize },
+    /// The handle's buffer stayed full for every attempt to signal it.
+    Full { category: String, index: usize },
+    /// The handle's thread is no longer receiving.
+    Disconnected { category: String, index: usize },
+    /// The handle's system function panicked while processing.
+    Panicked { category: String, index: usize },
+    /// The handle was registered in a way which does not support the operation.
+    Unsupported { category: String, index: usize },
+    /// The category's handles were still busy when the timeout elapsed.
+    Timeout(String),
+    /// A worker signaled its own handle while the handle's buffer was full, which would block the worker forever.
+    Reentrancy { category: String, index: usize },
+    /// The categories form a dependency cycle, each depending on the next and the last on the first.
+    Cycle(Vec<String>),
+    /// The manager is shutting down and no longer accepts systems, see `begin_shutdown`.
+    ShuttingDown,
+}
+
+impl fmt::Display for EcstaticError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EcstaticError::UnknownCategory(category) => write!(f, "unknown category \"{}\"", category),
+            EcstaticError::CategoryExists(category) => write!(f, "category \"{}\" already exists", category),
+            EcstaticError::UnknownHandle { category, index } => write!(f, "no handle {} in category \"{}\"", index, category),
+            EcstaticError::TypeMismatch { category, index } => write!(f, "handle {} in category \"{}\" was registered with different types", index, category),
+            EcstaticError::Full { category, index } => write!(f, "handle {} in category \"{}\" is full", index, category),
+            EcstaticError::Disconnected { category, index } => write!(f, "handle {} in category \"{}\" is disconnected", index, category),
+            EcstaticError::Panicked { category, index } => write!(f, "handle {} in category \"{}\" panicked", index, category),
+            EcstaticError::Unsupported { category, index } => write!(f, "handle {} in category \"{}\" does not support this operation", index, category),
+            EcstaticError::Timeout(category) => write!(f, "category \"{}\" did not become idle in time", category),
+            EcstaticError::Reentrancy { category, index } => write!(f, "handle {} in category \"{}\" signaled itself while full", index, category),
+            EcstaticError::Cycle(categories) => match categories.first() {
+                Some(first) => write!(f, "dependency cycle {} -> {}", categories.join(" -> "), first),
+                None => write!(f, "dependency cycle"),
+            },
+            EcstaticError::ShuttingDown => write!(f, "the manager is shutting down"),
+        }
+    }
+}
+
+impl Error for EcstaticError {}
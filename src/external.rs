@@ -0,0 +1,107 @@
+use std::any::Any;
+use std::marker::{Send, Sync};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::{data_slot, CancelToken, EcstaticSystems, ExitReason, SystemState, ThreadHandle};
+
+/// How long an external system waits for a message before checking whether its category is being torn down.
+const CANCEL_POLL: Duration = Duration::from_millis(10);
+
+impl EcstaticSystems {
+    /// Registers a system which runs once for every message received from a channel the caller already owns.
+    /// The worker exits once every sender of `rx` is dropped, or when its category is joined even while senders remain,
+    /// in which case messages still buffered are dropped. External systems are not signaled.
+    pub fn register_external<'a: 'static, T: Any + Send + Sync, M: Any + Send>(&mut self, category: &str, data: &'a T, rx: Receiver<M>, f: fn(Arc<&'a T>, M)) {
+        if self.is_shutting_down() {
+            return;
+        }
+        let token = CancelToken::new();
+        let worker_token = token.clone();
+        let data = data_slot(data);
+        let worker_data = data.clone();
+        let reporter = self.reporter(category);
+        let state = Arc::new(SystemState::identified(reporter.id()));
+        let worker_state = state.clone();
+        let handle = self.spawn_thread(category, move || {
+            while !worker_token.is_cancelled() {
+                let msg = match rx.recv_timeout(CANCEL_POLL) {
+                    Ok(msg) => msg,
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                };
+                worker_state.received();
+                let start = Instant::now();
+                if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| f(worker_data.load_full(), msg))) {
+                    reporter.panicked(payload.as_ref());
+                    worker_state.exited(ExitReason::Panicked);
+                    return;
+                }
+                worker_state.complete(start.elapsed());
+            }
+            worker_state.exited(ExitReason::Normal);
+        });
+        let th = ThreadHandle {
+            thread_id: Some(handle.thread().id()),
+            join_handle: Some(handle),
+            cancel: Some(token),
+            state,
+            data: Some(data),
+            ..Default::default()
+        };
+        self.push_handle(category, th);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem;
+    use std::sync::mpsc;
+    use std::sync::Mutex;
+    use std::time::{Duration, Instant};
+
+    use crate::test_util::{assert_invocations, pump_until};
+    use crate::{EcstaticSystems, ExitReason, HandleStatus};
+
+    #[test]
+    fn test_external_exits_with_sender() {
+        let mut sys = EcstaticSystems::new();
+        static SEEN: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+        let (sx, rx) = mpsc::channel();
+        sys.register_external("testing", &SEEN, rx, |seen, msg: u32| seen.lock().unwrap().push(msg));
+        for msg in 0..3 {
+            sx.send(msg).unwrap();
+        }
+        assert_invocations(&sys, "testing", 0, 3);
+        assert!(*SEEN.lock().unwrap() == vec![0, 1, 2]);
+        sys.signal("testing");
+        assert!(sys.handle_status("testing", 0) == Ok(HandleStatus::Running));
+
+        mem::drop(sx);
+        pump_until(&sys, |sys| sys.handle_status("testing", 0) == Ok(HandleStatus::Exited));
+        assert!(sys.exit_reason("testing", 0) == Some(ExitReason::Normal));
+        sys.drop_join_category("testing");
+        assert!(SEEN.lock().unwrap().len() == 3);
+    }
+
+    #[test]
+    fn test_external_joins_while_sender_remains() {
+        let mut sys = EcstaticSystems::new();
+        static SEEN: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+        let (sx, rx) = mpsc::sync_channel(10);
+        sys.register_external("testing", &SEEN, rx, |seen, msg: u32| seen.lock().unwrap().push(msg));
+        sx.send(7).unwrap();
+        assert_invocations(&sys, "testing", 0, 1);
+
+        let start = Instant::now();
+        sys.drop_join_category("testing");
+        assert!(start.elapsed() < Duration::from_secs(2), "Took: {:?}", start.elapsed());
+        assert!(sys.exit_reason("testing", 0) == Some(ExitReason::Normal));
+        assert!(sx.send(8).is_err());
+        assert!(*SEEN.lock().unwrap() == vec![7]);
+    }
+}
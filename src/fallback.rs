@@ -0,0 +1,77 @@
+use std::any::Any;
+use std::marker::{Send, Sync};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+
+use super::{data_slot, EcstaticSystems};
+
+impl EcstaticSystems {
+    /// Registers a system like `register_static` which runs `primary` until it panics, and `fallback` for every
+    /// signal after that. The primary's panic is reported to the error sink, but the worker keeps running instead
+    /// of following the category's panic policy, which only applies to panics in `fallback`.
+    pub fn register_with_fallback<'a: 'static, T: Any + Send + Sync>(&mut self, category: &str, data: &'a T, primary: fn(Arc<&'a T>), fallback: fn(Arc<&'a T>)) {
+        if self.is_shutting_down() {
+            return;
+        }
+        let data = data_slot(data);
+        let worker_data = data.clone();
+        let reporter = self.reporter(category);
+        let worker_reporter = reporter.clone();
+        let mut th = self.system_create_reporting(category, reporter, || false, move |failed| {
+            if *failed {
+                fallback(worker_data.load_full());
+            } else if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| primary(worker_data.load_full()))) {
+                worker_reporter.panicked(payload.as_ref());
+                *failed = true;
+            }
+            true
+        }, |_| ());
+        th.data = Some(data);
+        self.push_handle(category, th);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use crate::test_util::assert_invocations;
+    use crate::{EcstaticSystems, ErrorEvent, HandleStatus};
+
+    static HANDLED: Mutex<Vec<(&str, usize)>> = Mutex::new(Vec::new());
+
+    fn primary(x: Arc<&AtomicUsize>) {
+        let run = x.fetch_add(1, Ordering::SeqCst);
+        if run == 2 {
+            panic!("primary failed");
+        }
+        HANDLED.lock().unwrap().push(("primary", run));
+    }
+
+    fn fallback(x: Arc<&AtomicUsize>) {
+        HANDLED.lock().unwrap().push(("fallback", x.fetch_add(1, Ordering::SeqCst)));
+    }
+
+    #[test]
+    fn test_register_with_fallback() {
+        static EVENTS: Mutex<Vec<ErrorEvent>> = Mutex::new(Vec::new());
+        let mut sys = EcstaticSystems::builder()
+            .error_sink(|e: &ErrorEvent| EVENTS.lock().unwrap().push(e.clone()))
+            .build();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_with_fallback("testing", &ATOMIC, primary, fallback);
+        for _ in 0..5 {
+            sys.signal("testing");
+        }
+        assert_invocations(&sys, "testing", 0, 5);
+        assert!(sys.handle_status("testing", 0) == Ok(HandleStatus::Running));
+        sys.clear();
+
+        let handled = HANDLED.lock().unwrap().clone();
+        assert!(handled == vec![("primary", 0), ("primary", 1), ("fallback", 3), ("fallback", 4)], "Actual: {:?}", handled);
+        let events = EVENTS.lock().unwrap().clone();
+        assert!(events == vec![ErrorEvent::Panicked { category: String::from("testing"), index: 0, message: String::from("primary failed") }], "Actual: {:?}", events);
+    }
+}
@@ -0,0 +1,51 @@
+use std::sync::atomic::Ordering;
+
+use super::EcstaticSystems;
+
+impl EcstaticSystems {
+    /// Makes every signaling method a no-op until `unfreeze` is called. Signals sent while frozen are dropped,
+    /// not buffered. Timers created by `schedule` keep ticking.
+    pub fn freeze(&self) {
+        self.frozen.store(true, Ordering::SeqCst);
+    }
+
+    pub fn unfreeze(&self) {
+        self.frozen.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use crate::test_util::assert_invocations;
+    use crate::EcstaticSystems;
+
+    #[test]
+    fn test_freeze() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.freeze();
+        assert!(sys.is_frozen());
+        sys.signal("testing");
+        sys.signal_all();
+        assert!(sys.signal_all_nonblocking() == 0);
+        assert!(sys.signal_round("testing") == Ok(0));
+        assert!(sys.step(Duration::from_secs(1)).completed == 0);
+
+        sys.unfreeze();
+        sys.signal("testing");
+        sys.signal_all();
+        assert_invocations(&sys, "testing", 0, 2);
+        sys.drop_join_category("testing");
+
+        assert!(ATOMIC.load(Ordering::Relaxed) == 2, "Actual: {:?}", ATOMIC.load(Ordering::Relaxed));
+    }
+}
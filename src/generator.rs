@@ -0,0 +1,85 @@
+use std::any::Any;
+use std::marker::{Send, Sync};
+use std::sync::Arc;
+
+use super::{data_slot, CancelToken, EcstaticSystems};
+
+/// Returned by the function of a stepped system to say whether the current signal needs more work.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SystemStep {
+    Continue,
+    Done,
+}
+
+impl EcstaticSystems {
+    /// Registers a system whose work for each signal is split into chunks.
+    /// `f` is called repeatedly until it returns `SystemStep::Done`, and the worker stops calling it
+    /// between chunks once its category is being torn down.
+    pub fn register_stepped<'a: 'static, T: Any + Send + Sync>(&mut self, category: &str, data: &'a T, f: fn(Arc<&'a T>) -> SystemStep) {
+        if self.is_shutting_down() {
+            return;
+        }
+        let token = CancelToken::new();
+        let worker_token = token.clone();
+        let data = data_slot(data);
+        let worker_data = data.clone();
+        let mut th = self.system_create(category, move || {
+            while !worker_token.is_cancelled() {
+                if f(worker_data.load_full()) == SystemStep::Done {
+                    break;
+                }
+            }
+        });
+        th.cancel = Some(token);
+        th.data = Some(data);
+        self.push_handle(category, th);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use super::SystemStep;
+    use crate::test_util::pump_until;
+    use crate::EcstaticSystems;
+
+    #[test]
+    fn test_stepped_chunks() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_stepped("testing", &ATOMIC, |x| {
+            if (x.fetch_add(1, Ordering::SeqCst) + 1) % 3 == 0 {
+                SystemStep::Done
+            } else {
+                SystemStep::Continue
+            }
+        });
+        sys.step(Duration::from_secs(5));
+        assert!(ATOMIC.load(Ordering::Relaxed) == 3);
+        sys.step(Duration::from_secs(5));
+        assert!(ATOMIC.load(Ordering::Relaxed) == 6);
+        sys.drop_join_category("testing");
+    }
+
+    #[test]
+    fn test_stepped_exits_mid_chunk() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_stepped("testing", &ATOMIC, |x| {
+            x.fetch_add(1, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(1));
+            SystemStep::Continue
+        });
+        sys.signal("testing");
+        pump_until(&sys, |_| ATOMIC.load(Ordering::SeqCst) > 0);
+
+        let start = Instant::now();
+        sys.drop_join_category("testing");
+        assert!(start.elapsed() < Duration::from_secs(2), "Took: {:?}", start.elapsed());
+    }
+}
@@ -0,0 +1,258 @@
+use std::time::{Duration, Instant};
+
+use super::{EcstaticError, EcstaticSystems, EcstaticSystemsBuilder, ErrorEvent, SchedulerClass, ThreadHandle};
+
+/// How often a wait on a worker, in `signal_graph`, `wait_for_until` or `wait_any`, checks whether the worker has exited.
+pub(crate) const EXIT_POLL: Duration = Duration::from_millis(10);
+
+/// A handle signaled by `signal_graph`, with its category's class and the signal count it has to complete.
+type Outstanding<'a> = (&'a String, SchedulerClass, &'a ThreadHandle, usize);
+
+impl EcstaticSystemsBuilder {
+    /// Lets `signal_graph` signal a category without waiting for categories of a lower `SchedulerClass`,
+    /// even when it depends on them, so a slow background category cannot hold up a realtime one.
+    pub fn priority_bypass(mut self, bypass: bool) -> EcstaticSystemsBuilder {
+        self.priority_bypass = bypass;
+        self
+    }
+}
+
+impl EcstaticSystems {
+    /// Declares that `category` must run after `on` in `signal_graph`.
+    /// Dependencies on categories which are not registered when the graph is signaled are ignored.
+    pub fn depends_on(&mut self, category: &str, on: &str) {
+        let deps = self.dependencies.entry(String::from(category)).or_default();
+        if !deps.iter().any(|dep| dep == on) {
+            deps.push(String::from(on));
+        }
+    }
+
+    /// Signals every category in dependency order, waiting for each level of the graph to finish before
+    /// signaling the categories which depend on it. Categories in the same level are signaled in registration order.
+    /// Nothing is signaled if the dependencies contain a cycle.
+    ///
+    /// A category about to be signaled while a category of a lower `SchedulerClass` from an earlier level is still
+    /// running is a priority inversion, reported to the error sink as `ErrorEvent::PriorityInversion`. With
+    /// `EcstaticSystemsBuilder::priority_bypass` such categories are signaled without waiting for the lower classes,
+    /// and each level is signaled from the highest class to the lowest. Returns once every category has finished.
+    pub fn signal_graph(&self) -> Result<(), EcstaticError> {
+        let levels = self.graph_levels()?;
+        if self.is_frozen() {
+            return Ok(());
+        }
+        let mut outstanding: Vec<Outstanding> = Vec::new();
+        for mut level in levels {
+            if self.priority_bypass {
+                level.sort_by_key(|category| self.class(category));
+            }
+            let mut signaled = Vec::new();
+            for category in level {
+                let class = self.class(category);
+                self.wait_outstanding(category, class, &mut outstanding);
+                signaled.extend(self.handles[category].iter()
+                    .enumerate()
+                    .filter_map(|(index, th)| Some((category, class, th, self.signal_handle(category, index, th)?))));
+            }
+            outstanding.append(&mut signaled);
+        }
+        for (_, _, th, target) in outstanding {
+            wait_for(th, target);
+        }
+        Ok(())
+    }
+
+    /// Waits for the signaled work which has to finish before `category` runs, reporting any of it from a lower
+    /// class which is still running. With `priority_bypass` the lower classes are left outstanding instead.
+    fn wait_outstanding(&self, category: &str, class: SchedulerClass, outstanding: &mut Vec<Outstanding>) {
+        let mut blocked_by: Vec<String> = Vec::new();
+        for (other, _, _, _) in outstanding.iter().filter(|(_, other_class, th, target)| *other_class > class && is_running(th, *target)) {
+            if !blocked_by.iter().any(|blocked| blocked == *other) {
+                blocked_by.push(String::from(other.as_str()));
+            }
+        }
+        if !blocked_by.is_empty() {
+            if let Some(sink) = self.error_sink.as_ref() {
+                sink.report(&ErrorEvent::PriorityInversion { category: String::from(category), blocked_by, bypassed: self.priority_bypass });
+            }
+        }
+        outstanding.retain(|(_, other_class, th, target)| {
+            if self.priority_bypass && *other_class > class {
+                return true;
+            }
+            wait_for(th, *target);
+            false
+        });
+    }
+
+    fn dependencies_of<'a>(&'a self, category: &'a str) -> impl Iterator<Item = &'a String> {
+        self.dependencies.get(category)
+            .into_iter()
+            .flatten()
+            .filter(move |dep| self.handles.contains_key(*dep))
+    }
+
+    fn graph_levels(&self) -> Result<Vec<Vec<&String>>, EcstaticError> {
+        let mut remaining: Vec<&String> = self.order.iter().collect();
+        let mut levels: Vec<Vec<&String>> = Vec::new();
+        while !remaining.is_empty() {
+            let (ready, blocked): (Vec<&String>, Vec<&String>) = remaining.iter()
+                .partition(|category| self.dependencies_of(category).all(|dep| !remaining.contains(&dep)));
+            if ready.is_empty() {
+                return Err(EcstaticError::Cycle(self.find_cycle(&blocked)));
+            }
+            levels.push(ready);
+            remaining = blocked;
+        }
+        Ok(levels)
+    }
+
+    /// Every blocked category depends on another blocked category, so following those dependencies
+    /// from any of them must eventually revisit one.
+    fn find_cycle(&self, blocked: &[&String]) -> Vec<String> {
+        let mut path: Vec<&String> = vec![blocked[0]];
+        loop {
+            let next = self.dependencies_of(path[path.len() - 1]).find(|dep| blocked.contains(dep)).unwrap();
+            if let Some(start) = path.iter().position(|category| *category == next) {
+                return path[start..].iter().map(|category| String::from(category.as_str())).collect();
+            }
+            path.push(next);
+        }
+    }
+}
+
+/// Waits for a worker to complete `target` signals, giving up if its thread exits first.
+/// Handles without a thread of their own are not waited on.
+pub(crate) fn wait_for(th: &ThreadHandle, target: usize) {
+    let join_handle = match th.join_handle.as_ref() {
+        Some(join_handle) => join_handle,
+        None => return,
+    };
+    while !th.state.wait_until(target, Instant::now() + EXIT_POLL) && !join_handle.is_finished() {}
+}
+
+/// Whether a worker with a thread of its own is still working towards `target` completed signals.
+pub(crate) fn is_running(th: &ThreadHandle, target: usize) -> bool {
+    th.join_handle.as_ref().is_some_and(|join_handle| !join_handle.is_finished()) && th.state.completed() < target
+}
+
+/// Like `wait_for`, but gives up once `deadline` passes. Returns whether the worker completed `target` signals or exited.
+pub(crate) fn wait_for_until(th: &ThreadHandle, target: usize, deadline: Instant) -> bool {
+    let join_handle = match th.join_handle.as_ref() {
+        Some(join_handle) => join_handle,
+        None => return true,
+    };
+    loop {
+        if th.state.wait_until(target, deadline.min(Instant::now() + EXIT_POLL)) || join_handle.is_finished() {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crate::{CollectingSink, EcstaticError, EcstaticSystems, ErrorEvent, SchedulerClass};
+
+    static RAN: Mutex<Vec<&str>> = Mutex::new(Vec::new());
+
+    fn position(ran: &[&str], category: &str) -> usize {
+        ran.iter().position(|c| *c == category).unwrap()
+    }
+
+    #[test]
+    fn test_signal_graph_order() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("render", &ATOMIC, |_| RAN.lock().unwrap().push("render"));
+        sys.register_static("audio", &ATOMIC, |_| RAN.lock().unwrap().push("audio"));
+        sys.register_static("physics", &ATOMIC, |_: Arc<&AtomicUsize>| {
+            thread::sleep(Duration::from_millis(20));
+            RAN.lock().unwrap().push("physics");
+        });
+        sys.register_static("input", &ATOMIC, |_: Arc<&AtomicUsize>| {
+            thread::sleep(Duration::from_millis(20));
+            RAN.lock().unwrap().push("input");
+        });
+        sys.depends_on("physics", "input");
+        sys.depends_on("render", "physics");
+        sys.depends_on("audio", "input");
+        sys.depends_on("audio", "missing");
+        sys.signal_graph().unwrap();
+
+        let ran = RAN.lock().unwrap().clone();
+        assert!(ran.len() == 4, "Actual: {:?}", ran);
+        assert!(position(&ran, "input") < position(&ran, "physics"), "Actual: {:?}", ran);
+        assert!(position(&ran, "input") < position(&ran, "audio"), "Actual: {:?}", ran);
+        assert!(position(&ran, "physics") < position(&ran, "render"), "Actual: {:?}", ran);
+        sys.clear();
+    }
+
+    #[test]
+    fn test_signal_graph_cycle() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        for category in ["root", "a", "b", "c"].iter() {
+            sys.register_static(category, &ATOMIC, |_| panic!("a cyclic graph must not be signaled"));
+        }
+        sys.depends_on("a", "root");
+        sys.depends_on("a", "c");
+        sys.depends_on("b", "a");
+        sys.depends_on("c", "b");
+
+        let result = sys.signal_graph();
+        let cycle = vec![String::from("a"), String::from("c"), String::from("b")];
+        assert!(result == Err(EcstaticError::Cycle(cycle)), "Actual: {:?}", result);
+        assert!(result.unwrap_err().to_string() == "dependency cycle a -> c -> b -> a");
+        assert!(EcstaticError::Cycle(Vec::new()).to_string() == "dependency cycle");
+        sys.clear();
+    }
+
+    #[test]
+    fn test_signal_graph_priority_inversion() {
+        static STARTED: Mutex<Vec<(&str, Instant)>> = Mutex::new(Vec::new());
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        const SLOW: Duration = Duration::from_millis(300);
+        let bound = Duration::from_millis(150);
+
+        for bypass in [false, true] {
+            let sink = CollectingSink::new();
+            let mut sys = EcstaticSystems::builder().error_sink(sink.clone()).priority_bypass(bypass).build();
+            sys.register_static("logging", &ATOMIC, |_: Arc<&AtomicUsize>| thread::sleep(SLOW));
+            sys.register_static("audio", &ATOMIC, |_| STARTED.lock().unwrap().push(("audio", Instant::now())));
+            sys.register_static("mixer", &ATOMIC, |_| STARTED.lock().unwrap().push(("mixer", Instant::now())));
+            sys.set_class("logging", SchedulerClass::Background);
+            sys.set_class("audio", SchedulerClass::Realtime);
+            sys.set_class("mixer", SchedulerClass::Realtime);
+            sys.depends_on("audio", "logging");
+            sys.depends_on("mixer", "audio");
+            STARTED.lock().unwrap().clear();
+
+            let start = Instant::now();
+            sys.signal_graph().unwrap();
+            assert!(start.elapsed() >= SLOW, "Actual: {:?}", start.elapsed());
+            let delays: Vec<_> = STARTED.lock().unwrap().iter().map(|(category, at)| (*category, at.duration_since(start))).collect();
+            assert!(delays.len() == 2 && delays[0].0 == "audio", "Actual: {:?}", delays);
+            if bypass {
+                assert!(delays.iter().all(|(_, delay)| *delay < bound), "Actual: {:?}", delays);
+            } else {
+                assert!(delays.iter().all(|(_, delay)| *delay >= SLOW), "Actual: {:?}", delays);
+            }
+            let inverted = if bypass { vec!["audio", "mixer"] } else { vec!["audio"] };
+            let expected: Vec<_> = inverted.into_iter()
+                .map(|category| ErrorEvent::PriorityInversion { category: String::from(category), blocked_by: vec![String::from("logging")], bypassed: bypass })
+                .collect();
+            let events = sink.take();
+            assert!(events == expected, "Actual: {:?}", events);
+            sys.clear();
+        }
+    }
+}
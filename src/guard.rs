@@ -0,0 +1,70 @@
+use std::ops::{Deref, DerefMut};
+
+use super::EcstaticSystems;
+
+/// Drops and joins a category when it goes out of scope, see `category_guard`.
+/// The manager stays usable through the guard while it is alive.
+pub struct CategoryGuard<'a> {
+    sys: &'a mut EcstaticSystems,
+    category: String,
+}
+
+impl<'a> CategoryGuard<'a> {
+    pub fn category(&self) -> &str {
+        &self.category
+    }
+}
+
+impl<'a> Deref for CategoryGuard<'a> {
+    type Target = EcstaticSystems;
+
+    fn deref(&self) -> &EcstaticSystems {
+        self.sys
+    }
+}
+
+impl<'a> DerefMut for CategoryGuard<'a> {
+    fn deref_mut(&mut self) -> &mut EcstaticSystems {
+        self.sys
+    }
+}
+
+impl<'a> Drop for CategoryGuard<'a> {
+    fn drop(&mut self) {
+        self.sys.drop_join_category(&self.category);
+    }
+}
+
+impl EcstaticSystems {
+    /// Borrows the manager until the returned guard is dropped, at which point `category` is dropped and joined,
+    /// including on early return or while unwinding.
+    pub fn category_guard(&mut self, category: &str) -> CategoryGuard<'_> {
+        CategoryGuard { sys: self, category: String::from(category) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::EcstaticSystems;
+
+    #[test]
+    fn test_category_guard() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("outer", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        {
+            let mut guard = sys.category_guard("scoped");
+            guard.register_static("scoped", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+            guard.signal(guard.category());
+            assert!(guard.has_category("scoped"));
+        }
+
+        assert!(!sys.has_category("scoped"));
+        assert!(sys.has_category("outer"));
+        assert!(ATOMIC.load(Ordering::SeqCst) == 1, "Actual: {:?}", ATOMIC.load(Ordering::SeqCst));
+        sys.clear();
+    }
+}
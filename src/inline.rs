@@ -0,0 +1,108 @@
+use std::any::Any;
+use std::marker::{Send, Sync};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use super::channel::signal_channel;
+use super::{data_slot, EcstaticSystems, SystemState, ThreadHandle};
+
+/// Runs at most one buffered signal of an inline system, returning whether it ran.
+pub(crate) type InlineRunner = Mutex<Box<dyn FnMut() -> bool + Send>>;
+
+impl EcstaticSystems {
+    /// Registers a system without a thread of its own. Signals are buffered as usual but only run
+    /// when the owner calls `poll_once`, on the calling thread, which allows interleaving systems with an external event loop.
+    /// Signals sent while the buffer is full are dropped and reported as `ErrorEvent::SignalDropped`, rather than blocking.
+    pub fn register_inline<'a: 'static, T: Any + Send + Sync>(&mut self, category: &str, data: &'a T, f: fn(Arc<&'a T>)) {
+        if self.is_shutting_down() {
+            return;
+        }
+        let th = self.inline_system_create(category, data, f);
+        self.push_handle(category, th);
+    }
+
+    pub(crate) fn inline_system_create<'a: 'static, T: Any + Send + Sync>(&self, category: &str, data: &'a T, f: fn(Arc<&'a T>)) -> ThreadHandle {
+        let (sx, rx, capacity) = signal_channel(self.channel_kind(category));
+        let state = Arc::new(SystemState::default());
+        let runner_state = state.clone();
+        let data = data_slot(data);
+        let runner_data = data.clone();
+        let runner = move || {
+            if rx.try_recv().is_err() {
+                return false;
+            }
+            runner_state.received();
+            let start = Instant::now();
+            f(runner_data.load_full());
+            runner_state.complete(start.elapsed());
+            true
+        };
+        ThreadHandle {
+            sx: Some(sx),
+            state,
+            capacity,
+            data: Some(data),
+            inline: Some(Mutex::new(Box::new(runner))),
+            ..Default::default()
+        }
+    }
+
+    /// Runs at most one buffered signal for every inline system without blocking.
+    /// Returns how many systems ran.
+    pub fn poll_once(&self) -> usize {
+        self.order.iter()
+            .flat_map(|category| self.handles[category].iter())
+            .filter_map(|th| th.inline.as_ref())
+            .filter(|runner| (runner.lock().unwrap())())
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::{CategoryConfig, CollectingSink, EcstaticSystems, ErrorEvent};
+
+    #[test]
+    fn test_poll_once() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        static ATOMIC1: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_inline("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_inline("testing2", &ATOMIC1, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        for _ in 0..3 {
+            sys.signal_all();
+        }
+        assert!(ATOMIC.load(Ordering::Relaxed) == 0);
+
+        for expected in 1..=3 {
+            assert!(sys.poll_once() == 2);
+            assert!(ATOMIC.load(Ordering::Relaxed) == expected);
+            assert!(ATOMIC1.load(Ordering::Relaxed) == expected);
+        }
+        assert!(sys.poll_once() == 0);
+        assert!(sys.stats("testing", 0).unwrap().count == 3);
+        sys.clear();
+    }
+
+    #[test]
+    fn test_inline_full_buffer_drops() {
+        let sink = CollectingSink::new();
+        let mut sys = EcstaticSystems::builder().error_sink(sink.clone()).build();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.configure_category("testing", CategoryConfig { buffer_size: Some(2), ..Default::default() });
+        sys.register_inline("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        for _ in 0..5 {
+            sys.signal_all();
+        }
+        let events = sink.take();
+        assert!(events == vec![ErrorEvent::SignalDropped { category: String::from("testing"), index: 0 }; 3], "Actual: {:?}", events);
+
+        while sys.poll_once() > 0 {}
+        assert!(ATOMIC.load(Ordering::SeqCst) == 2, "Actual: {:?}", ATOMIC.load(Ordering::SeqCst));
+        sys.clear();
+    }
+}
@@ -0,0 +1,175 @@
+use super::{EcstaticError, EcstaticSystems};
+
+/// Whether a handle's worker is still running, see `handle_status`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HandleStatus {
+    /// The worker thread is running, or waiting for signals.
+    Running,
+    /// The worker thread has exited, so joining it will not block.
+    Exited,
+    /// The handle has no thread of its own, such as an inline or main thread system.
+    Inline,
+    /// The worker is alive but dropping signals after a panic, see `PanicPolicy::Disable`.
+    Disabled,
+}
+
+/// Why a worker thread stopped, see `exit_reason`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExitReason {
+    /// The worker's sender was dropped, usually because its category was joined.
+    Normal,
+    /// A self-terminating system returned false.
+    SelfTerminated,
+    /// The system panicked and its panic policy stopped the worker.
+    Panicked,
+    /// The worker thread unwound outside of its system, e.g. from a panicking `init` or teardown.
+    Aborted,
+}
+
+impl EcstaticSystems {
+    /// Why a handle's worker stopped, or `None` while it is still running or if it has no thread.
+    /// Reasons stay available after the category is dropped, until a system is registered into it again.
+    pub fn exit_reason(&self, category: &str, index: usize) -> Option<ExitReason> {
+        match self.handles.get(category) {
+            Some(ths) => {
+                let th = ths.get(index)?;
+                match th.state.exit_reason() {
+                    Some(reason) => Some(reason),
+                    None if th.join_handle.as_ref()?.is_finished() => Some(ExitReason::Aborted),
+                    None => None,
+                }
+            }
+            None => *self.exit_reasons.get(category)?.get(index)?,
+        }
+    }
+
+    /// Reports whether a handle's worker thread is still running.
+    pub fn handle_status(&self, category: &str, index: usize) -> Result<HandleStatus, EcstaticError> {
+        let th = self.handle(category, index)?;
+        Ok(match th.join_handle.as_ref() {
+            Some(join_handle) if join_handle.is_finished() => HandleStatus::Exited,
+            Some(_) if th.state.is_disabled() => HandleStatus::Disabled,
+            Some(_) => HandleStatus::Running,
+            None => HandleStatus::Inline,
+        })
+    }
+
+    /// Lists the categories, in registration order, with at least one handle whose buffer is currently full.
+    /// Handles with a zero capacity buffer have nothing to fill and are never counted.
+    pub fn saturated_categories(&self) -> Vec<String> {
+        self.order.iter()
+            .filter(|category| {
+                self.handles[*category].iter().any(|th| th.sx.is_some() && th.capacity > 0 && th.state.pending() >= th.capacity)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// The category with the most signals waiting in its handles' buffers, together with that total.
+    /// Ties go to the category registered first, and `None` is returned while nothing is waiting anywhere.
+    pub fn busiest_category(&self) -> Option<(String, usize)> {
+        self.order.iter()
+            .map(|category| (category, self.handles[category].iter().map(|th| th.state.pending()).sum::<usize>()))
+            .filter(|(_, pending)| *pending > 0)
+            .fold(None, |busiest: Option<(&String, usize)>, (category, pending)| match busiest {
+                Some((_, most)) if most >= pending => busiest,
+                _ => Some((category, pending)),
+            })
+            .map(|(category, pending)| (category.clone(), pending))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::{ExitReason, HandleStatus};
+    use crate::test_util::{gated, pump_until, Gated};
+    use crate::{CategoryConfig, EcstaticSystems};
+
+    #[test]
+    fn test_saturated_categories() {
+        let mut sys = EcstaticSystems::new();
+        static GATED: Gated = Gated::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.configure_category("full", CategoryConfig { buffer_size: Some(2), ..Default::default() });
+        sys.register_static("full", &GATED, gated);
+        sys.register_static("idle", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.configure_category("rendezvous", CategoryConfig { buffer_size: Some(0), ..Default::default() });
+        sys.register_static("rendezvous", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        assert!(sys.saturated_categories().is_empty());
+
+        sys.signal("full");
+        pump_until(&sys, |_| GATED.runs.load(Ordering::SeqCst) > 0);
+        sys.signal("full");
+        assert!(sys.saturated_categories().is_empty());
+        sys.signal("full");
+        sys.signal("idle");
+        assert!(sys.saturated_categories() == vec![String::from("full")], "Actual: {:?}", sys.saturated_categories());
+
+        GATED.released.store(true, Ordering::SeqCst);
+        sys.clear();
+        assert!(GATED.runs.load(Ordering::Relaxed) == 3);
+    }
+
+    #[test]
+    fn test_busiest_category() {
+        let mut sys = EcstaticSystems::new();
+        static GATED: Gated = Gated::new();
+        static GATED1: Gated = Gated::new();
+
+        sys.register_static("light", &GATED, gated);
+        sys.register_static("heavy", &GATED1, gated);
+        sys.register_static("heavy", &GATED1, gated);
+        assert!(sys.busiest_category().is_none());
+
+        sys.signal("light");
+        sys.signal("heavy");
+        pump_until(&sys, |_| GATED.runs.load(Ordering::SeqCst) == 1 && GATED1.runs.load(Ordering::SeqCst) == 2);
+        assert!(sys.busiest_category().is_none());
+        for _ in 0..2 {
+            sys.signal("light");
+            sys.signal("heavy");
+        }
+        let busiest = sys.busiest_category();
+        assert!(busiest == Some((String::from("heavy"), 4)), "Actual: {:?}", busiest);
+
+        GATED.released.store(true, Ordering::SeqCst);
+        GATED1.released.store(true, Ordering::SeqCst);
+        sys.clear();
+        assert!(GATED.runs.load(Ordering::Relaxed) == 3);
+        assert!(GATED1.runs.load(Ordering::Relaxed) == 6);
+    }
+
+    #[test]
+    fn test_exit_reason() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_self_terminating("testing", &ATOMIC, |_| false);
+        sys.register_static("testing", &ATOMIC, |_| panic!("system panicked"));
+        sys.register_stateful("testing", || -> usize { panic!("init panicked") }, |_| (), |_| ());
+        sys.signal("testing");
+        pump_until(&sys, |sys| (1..4).all(|index| sys.handle_status("testing", index) == Ok(HandleStatus::Exited)));
+
+        assert!(sys.exit_reason("testing", 0).is_none());
+        assert!(sys.exit_reason("testing", 1) == Some(ExitReason::SelfTerminated));
+        assert!(sys.exit_reason("testing", 2) == Some(ExitReason::Panicked));
+        assert!(sys.exit_reason("testing", 3) == Some(ExitReason::Aborted));
+        sys.drop_join_category("testing");
+
+        let reasons: Vec<_> = (0..5).map(|index| sys.exit_reason("testing", index)).collect();
+        assert!(reasons == vec![
+            Some(ExitReason::Normal),
+            Some(ExitReason::SelfTerminated),
+            Some(ExitReason::Panicked),
+            Some(ExitReason::Aborted),
+            None,
+        ], "Actual: {:?}", reasons);
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        assert!(sys.exit_reason("testing", 1).is_none());
+        sys.clear();
+    }
+}
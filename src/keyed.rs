@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::{EcstaticError, EcstaticSystems};
+
+/// The signal number each key was last delivered as, so it counts as in flight until the worker completes that many.
+pub(crate) type InFlightKeys = Mutex<HashMap<u64, usize>>;
+
+impl EcstaticSystems {
+    /// Signals every handle in a category like `signal_round`, except handles which have not yet finished an earlier
+    /// signal sent with the same `key`. Returns how many handles were signaled.
+    pub fn signal_keyed(&self, category: &str, key: u64) -> Result<usize, EcstaticError> {
+        let ths = self.handles.get(category).ok_or_else(|| EcstaticError::UnknownCategory(String::from(category)))?;
+        if self.is_frozen() {
+            return Ok(0);
+        }
+        let mut signaled = 0;
+        for (index, th) in ths.iter().enumerate() {
+            let mut keys = th.keys.lock().unwrap();
+            let completed = th.state.completed();
+            keys.retain(|_, target| completed < *target);
+            if keys.contains_key(&key) {
+                continue;
+            }
+            if let Some(target) = self.signal_handle(category, index, th) {
+                keys.insert(key, target);
+                signaled += 1;
+            }
+        }
+        Ok(signaled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use crate::test_util::{assert_invocations, gated, pump_until, Gated};
+    use crate::{EcstaticError, EcstaticSystems};
+
+    #[test]
+    fn test_signal_keyed() {
+        let mut sys = EcstaticSystems::new();
+        static GATED: Gated = Gated::new();
+
+        sys.register_static("testing", &GATED, gated);
+        assert!(sys.signal_keyed("testing", 7) == Ok(1));
+        pump_until(&sys, |_| GATED.runs.load(Ordering::SeqCst) == 1);
+        for _ in 0..4 {
+            assert!(sys.signal_keyed("testing", 7) == Ok(0));
+        }
+        assert!(sys.signal_keyed("missing", 7) == Err(EcstaticError::UnknownCategory(String::from("missing"))));
+
+        GATED.released.store(true, Ordering::SeqCst);
+        assert_invocations(&sys, "testing", 0, 1);
+        assert!(sys.signal_keyed("testing", 8) == Ok(1));
+        assert_invocations(&sys, "testing", 0, 2);
+        assert!(sys.signal_keyed("testing", 7) == Ok(1));
+        assert_invocations(&sys, "testing", 0, 3);
+        sys.clear();
+        assert!(GATED.runs.load(Ordering::SeqCst) == 3);
+    }
+}
@@ -0,0 +1,105 @@
+use std::time::{Duration, Instant};
+
+use super::graph::wait_for;
+use super::stats::mean;
+use super::EcstaticSystems;
+
+/// How long waited broadcasts took from the first signal until every system finished, see `tick_latency_stats`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LatencyStats {
+    /// How many waited broadcasts have been recorded.
+    pub count: usize,
+    /// The fastest broadcast, or zero if none have been recorded.
+    pub min: Duration,
+    /// The slowest broadcast, or zero if none have been recorded.
+    pub max: Duration,
+    /// The mean latency, or zero if none have been recorded.
+    pub avg: Duration,
+}
+
+/// Accumulates tick latencies. Kept in a `Mutex` so waited broadcasts can record through `&self`.
+#[derive(Default)]
+pub(crate) struct LatencyRecorder {
+    count: usize,
+    total: Duration,
+    min: Duration,
+    max: Duration,
+}
+
+impl LatencyRecorder {
+    pub(crate) fn record(&mut self, latency: Duration) {
+        self.min = if self.count == 0 { latency } else { self.min.min(latency) };
+        self.max = self.max.max(latency);
+        self.total += latency;
+        self.count += 1;
+    }
+
+    fn stats(&self) -> LatencyStats {
+        LatencyStats {
+            count: self.count,
+            min: self.min,
+            max: self.max,
+            avg: mean(self.total, self.count),
+        }
+    }
+}
+
+impl EcstaticSystems {
+    /// Signals every handle once and blocks until all of them finish, recording how long that took in
+    /// `tick_latency_stats`. Handles without a thread of their own are signaled but not waited on.
+    pub fn signal_all_and_wait(&self) {
+        if self.is_frozen() {
+            return;
+        }
+        let start = Instant::now();
+        let targets: Vec<_> = self.order.iter()
+            .flat_map(|category| self.handles[category].iter().enumerate().map(move |(index, th)| (category, index, th)))
+            .filter_map(|(category, index, th)| self.signal_handle(category, index, th).map(|target| (th, target)))
+            .collect();
+        for (th, target) in targets {
+            wait_for(th, target);
+        }
+        self.tick_latency.lock().unwrap().record(start.elapsed());
+    }
+
+    /// The latency of every `signal_all_and_wait`, and of every `step` which finished within its budget.
+    pub fn tick_latency_stats(&self) -> LatencyStats {
+        self.tick_latency.lock().unwrap().stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    use super::LatencyStats;
+    use crate::EcstaticSystems;
+
+    #[test]
+    fn test_tick_latency_stats() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        const SLEEP: Duration = Duration::from_millis(20);
+
+        sys.register_static("fast", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("slow", &ATOMIC, |x|{
+            thread::sleep(SLEEP);
+            x.fetch_add(1, Ordering::SeqCst);
+        });
+        assert!(sys.tick_latency_stats() == LatencyStats::default());
+        for _ in 0..3 {
+            sys.signal_all_and_wait();
+        }
+        assert!(ATOMIC.load(Ordering::SeqCst) == 6);
+        sys.step(Duration::from_secs(5));
+        sys.step(Duration::from_millis(1));
+
+        let stats = sys.tick_latency_stats();
+        assert!(stats.count == 4, "Actual: {:?}", stats);
+        assert!(stats.min >= SLEEP, "Actual: {:?}", stats);
+        assert!(stats.max >= stats.avg && stats.avg >= stats.min, "Actual: {:?}", stats);
+        sys.clear();
+    }
+}
@@ -2,112 +2,659 @@
 //  Replace instances of .unwrap() with proper error handling.
 //  Add testing for when a supplied thread function panics.
 
+//systems are handed their data as an Arc<&T>, so named system functions take one too.
+#![allow(clippy::redundant_allocation)]
+
 use std::mem;
-use std::sync::mpsc;
 use std::thread;
 use std::marker::{Send, Sync};
-use std::sync::mpsc::{SyncSender, Receiver, RecvError};
-use std::thread::JoinHandle;
-use std::sync::Arc;
+use std::thread::{JoinHandle, ThreadId};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::any::Any;
 use std::vec::Vec;
 use std::collections::HashMap;
-
-
+use std::time::{Duration, Instant};
+use std::panic::{self, AssertUnwindSafe};
+
+use arc_swap::ArcSwap;
+
+mod ack;
+#[cfg(feature = "core_affinity")]
+mod affinity;
+mod aggregate;
+mod alias;
+mod barrier;
+mod broadcast;
+mod budget;
+mod cancel;
+mod channel;
+mod class;
+#[cfg(feature = "async")]
+mod completion;
+#[cfg(feature = "dashmap")]
+mod component;
+mod config;
+mod data;
+mod error;
+mod external;
+mod fallback;
+mod freeze;
+mod generator;
+mod graph;
+mod guard;
+mod inline;
+mod introspect;
+mod keyed;
+mod latency;
+mod lifecycle;
+mod lifo;
+mod main_thread;
+mod monitor;
+mod policy;
+mod pool;
+mod receiver;
+mod record;
+mod reload;
+mod relocate;
+mod request;
+mod resize;
+mod retry;
+mod returning;
+mod sample;
+mod schedule;
+mod scratch;
+mod sequence;
+mod shared;
+mod shutdown;
+mod sink;
+mod spawner;
+mod spec;
+mod state;
+mod stateful;
+mod stats;
+mod step;
+mod teardown;
+#[cfg(feature = "metrics")]
+mod telemetry;
+mod terminating;
+mod transaction;
+mod typed;
+mod watchdog;
+mod weak;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+
+pub use alias::AliasMode;
+pub use budget::Deadline;
+pub use cancel::CancelToken;
+pub use channel::{ChannelKind, SignalSender};
+pub use class::SchedulerClass;
+#[cfg(feature = "async")]
+pub use completion::CompletionStream;
+pub use config::{CategoryConfig, DEFAULT_BUFFER_SIZE};
+#[cfg(feature = "dashmap")]
+pub use dashmap::DashMap;
+pub use error::EcstaticError;
+pub use generator::SystemStep;
+pub use guard::CategoryGuard;
+pub use introspect::{ExitReason, HandleStatus};
+pub use latency::LatencyStats;
+pub use monitor::SlowConsumer;
+pub use policy::{PanicPolicy, RestartDrain};
+pub use record::{SignalLog, SignalRecord};
+pub use resize::BufferGuard;
+pub use returning::{OverflowPolicy, ResultBuffer};
+pub use schedule::{Jitter, ScheduleHandle, ScheduleInfo};
+pub use scratch::Scratch;
+pub use shared::SharedSystems;
+pub use sink::{CollectingSink, ErrorEvent, ErrorSink};
+pub use spawner::Spawner;
+pub use spec::SystemSpec;
+pub use stats::SystemStats;
+pub use step::StepReport;
+pub use transaction::SignalTransaction;
+pub use typed::{Category, TypedSystems};
+pub use weak::WeakSystem;
+
+use state::SystemState;
+
+
+#[derive(Default)]
 pub struct ThreadHandle {
-    pub sx: Option<SyncSender<()>>,
+    pub sx: Option<SignalSender>,
     pub join_handle: Option<JoinHandle<()>>,
+    pub cancel: Option<CancelToken>,
+    pub key: Option<String>,
+    pub thread_id: Option<ThreadId>,
+    results: Option<Arc<dyn Any + Send + Sync>>,
+    final_state: Option<Arc<dyn Any + Send + Sync>>,
+    config: Option<Box<dyn Any + Send + Sync>>,
+    mx: Option<Box<dyn Any + Send + Sync>>,
+    state: Arc<SystemState>,
+    capacity: usize,
+    data: Option<Arc<dyn Any + Send + Sync>>,
+    inline: Option<inline::InlineRunner>,
+    next_rx: Option<resize::NextReceivers>,
+    ticks: Option<barrier::TickSlot>,
+    keys: keyed::InFlightKeys,
+    lifecycle: Option<lifecycle::Lifecycle>,
+    main: Option<inline::InlineRunner>,
+    respawn: Option<spec::Respawn>,
 }
 
-impl Default for ThreadHandle {
-    fn default() -> ThreadHandle {
-        ThreadHandle {
-            sx: None,
-            join_handle: None,
+/// Wraps a system's data so that it can be swapped while the system's thread is running.
+/// The worker loads the current data at the start of every invocation.
+fn data_slot<T: Any + Send + Sync>(data: &'static T) -> Arc<ArcSwap<&'static T>> {
+    Arc::new(ArcSwap::from_pointee(data))
+}
+
+impl ThreadHandle {
+    /// The category the handle is registered in, kept up to date when it moves.
+    pub fn category(&self) -> String {
+        self.state.id().map(|(category, _)| category).unwrap_or_default()
+    }
+
+    /// The handle's position in its category, kept up to date when it moves or handles before it move away.
+    pub fn index(&self) -> usize {
+        self.state.id().map_or(0, |(_, index)| index)
+    }
+
+    /// Sends a signal to the worker, returning how many signals have been delivered to it in total.
+    /// Returns `None` if the handle is not signaled or its worker has stopped receiving.
+    fn send_signal(&self) -> Option<usize> {
+        let sx = self.sx.as_ref()?;
+        sx.send(()).ok()?;
+        Some(self.state.signaled())
+    }
+
+    /// Whether the caller is running on the handle's own worker thread.
+    fn is_own_thread(&self) -> bool {
+        self.join_handle.is_some() && self.thread_id == Some(thread::current().id())
+    }
+
+    /// Sends a signal to the worker unless its buffer is full, returning whether it was sent.
+    fn try_send_signal(&self) -> bool {
+        match self.sx.as_ref().map(|sx| sx.try_send(())) {
+            Some(Ok(())) => {
+                self.state.signaled();
+                true
+            }
+            _ => false,
         }
     }
 }
 
 pub struct EcstaticSystems {
     handles: HashMap<String, Vec<ThreadHandle>>,
+    order: Vec<String>,
+    configs: HashMap<String, CategoryConfig>,
+    aliases: HashMap<String, (String, AliasMode)>,
+    cursor: AtomicUsize,
+    error_sink: Option<Arc<dyn ErrorSink>>,
+    panic_downcasts: sink::PanicDowncasts,
+    watchdog: Option<Duration>,
+    priority_bypass: bool,
+    schedules: HashMap<u64, schedule::Schedule>,
+    next_schedule: u64,
+    main_thread: ThreadId,
+    dependencies: HashMap<String, Vec<String>>,
+    frozen: AtomicBool,
+    recording: Mutex<Option<record::Recording>>,
+    aggregators: HashMap<String, aggregate::Aggregator>,
+    exit_reasons: HashMap<String, Vec<Option<ExitReason>>>,
+    tick_lock: Mutex<()>,
+    monitors: Vec<monitor::Monitor>,
+    spawner: Option<Spawner>,
+    tick_latency: Mutex<latency::LatencyRecorder>,
+    spawned: AtomicUsize,
+    joined: AtomicUsize,
+    shutting_down: AtomicBool,
+}
+
+/// Configures an `EcstaticSystems` before it is created, see `EcstaticSystems::builder`.
+#[derive(Default)]
+pub struct EcstaticSystemsBuilder {
+    error_sink: Option<Arc<dyn ErrorSink>>,
+    watchdog: Option<Duration>,
+    priority_bypass: bool,
+    spawner: Option<Spawner>,
+}
+
+impl EcstaticSystemsBuilder {
+    pub fn build(self) -> EcstaticSystems {
+        let mut sys = EcstaticSystems::new();
+        sys.error_sink = self.error_sink;
+        sys.watchdog = self.watchdog;
+        sys.priority_bypass = self.priority_bypass;
+        sys.spawner = self.spawner;
+        sys
+    }
+}
+
+impl Default for EcstaticSystems {
+    fn default() -> EcstaticSystems {
+        EcstaticSystems::new()
+    }
 }
 
 impl EcstaticSystems {
     pub fn new() -> EcstaticSystems {
         EcstaticSystems { 
             handles: HashMap::new(),
+            order: Vec::new(),
+            configs: HashMap::new(),
+            aliases: HashMap::new(),
+            cursor: AtomicUsize::new(0),
+            error_sink: None,
+            panic_downcasts: Arc::default(),
+            watchdog: None,
+            priority_bypass: false,
+            schedules: HashMap::new(),
+            next_schedule: 0,
+            main_thread: thread::current().id(),
+            dependencies: HashMap::new(),
+            frozen: AtomicBool::new(false),
+            recording: Mutex::new(None),
+            aggregators: HashMap::new(),
+            exit_reasons: HashMap::new(),
+            tick_lock: Mutex::new(()),
+            monitors: Vec::new(),
+            spawner: None,
+            tick_latency: Mutex::default(),
+            spawned: AtomicUsize::new(0),
+            joined: AtomicUsize::new(0),
+            shutting_down: AtomicBool::new(false),
         }
     }
 
+    pub fn builder() -> EcstaticSystemsBuilder {
+        EcstaticSystemsBuilder::default()
+    }
+
     /// Sends a signal to every possible thread handle amongst all categories.
+    ///
+    /// Every category registered when the call starts is signaled exactly once, looking each one up only once.
+    /// Through `SharedSystems` registration waits until it returns.
     pub fn signal_all(&self) {
-        for k in self.handles.keys() {
-            self.signal(k);
+        if self.is_frozen() {
+            return;
+        }
+        for (category, ths) in self.handles.iter() {
+            self.signal_handles(category, ths);
         }
     }
 
-    /// Sends a signal to every thread handle in a category.
+    /// Sends a signal to every thread handle in a category, following any alias set by `alias_category`.
     pub fn signal(&self, category: &str) {
-        for th in self.handles.get(category).unwrap().iter() {
-            th.sx.as_ref().unwrap().send(());
+        if self.is_frozen() {
+            return;
+        }
+        match self.aliases.get(category) {
+            Some((to, AliasMode::Redirect)) => self.signal_direct(to),
+            Some((to, AliasMode::Mirror)) => {
+                self.signal_direct(category);
+                self.signal_direct(to);
+            }
+            None => self.signal_direct(category),
+        }
+    }
+
+    fn signal_direct(&self, category: &str) {
+        self.signal_handles(category, self.handles.get(category).unwrap());
+    }
+
+    fn signal_handles(&self, category: &str, ths: &[ThreadHandle]) {
+        self.record(category);
+        for (index, th) in ths.iter().enumerate() {
+            self.signal_handle(category, index, th);
         }
     }
 
     /// Registers a system which will run on its own thread, but only operates when given a signal through its sender.
     pub fn register_static<'a: 'static, T: Any + Send + Sync>(&mut self, category: &str, data: &'a T, f: fn(Arc<&'a T>)) {
-        let th = self.static_system_create(data, f);
+        if self.is_shutting_down() {
+            return;
+        }
+        let th = self.static_system_create(category, data, f);
+        self.push_handle(category, th);
+    }
+
+    /// Registers a static system identified by `key`, unless a system with the same key is already in the category.
+    /// Returns whether the system was registered.
+    pub fn register_static_once<'a: 'static, T: Any + Send + Sync>(&mut self, category: &str, key: &str, data: &'a T, f: fn(Arc<&'a T>)) -> bool {
+        let exists = self.handles.get(category)
+            .map(|ths| ths.iter().any(|th| th.key.as_deref() == Some(key)))
+            .unwrap_or(false);
+        if exists || self.is_shutting_down() {
+            return false;
+        }
+        let mut th = self.static_system_create(category, data, f);
+        th.key = Some(String::from(key));
+        self.push_handle(category, th);
+        true
+    }
+
+    /// The id of the thread running a handle's system.
+    pub fn thread_id(&self, category: &str, index: usize) -> Option<ThreadId> {
+        self.handle(category, index).ok()?.thread_id
+    }
+
+    /// Borrows the handles registered in a category, in registration order.
+    pub fn handles(&self, category: &str) -> Option<&[ThreadHandle]> {
+        self.handles.get(category).map(Vec::as_slice)
+    }
+
+    /// Whether a category has been registered and not dropped, so it can be signaled without panicking.
+    pub fn has_category(&self, category: &str) -> bool {
+        self.handles.contains_key(category)
+    }
+
+    /// Registers a category without any systems, so it can be signaled as a no-op until systems are registered into it.
+    pub fn create_category(&mut self, category: &str) -> Result<(), EcstaticError> {
+        if self.is_shutting_down() {
+            return Err(EcstaticError::ShuttingDown);
+        }
+        if self.has_category(category) {
+            return Err(EcstaticError::CategoryExists(String::from(category)));
+        }
         self.lazy_init_category(category);
-        self.handles.get_mut(category).unwrap().push(th);
+        Ok(())
     }
 
     /// Drops the senders for a thread category and joins each thread in the category.
+    /// Threads are joined in the reverse order that they were registered.
     pub fn drop_join_category(&mut self, category: &str) {
+        self.cancel_category_schedules(category);
         if let Some(ths) = self.handles.get_mut(category) {
-            for th in ths.iter_mut() {
-                let mut handle = mem::take(th);
-                mem::drop(handle.sx.take());
-                handle.join_handle.take().unwrap().join();
-            }
+            let reasons = join_handles(ths, &self.joined);
+            self.exit_reasons.insert(String::from(category), reasons);
         }
         self.handles.remove(category);
+        self.aggregators.remove(category);
+        self.order.retain(|c| c != category);
     }
 
-    fn lazy_init_category(&mut self, category: &str) {
-        let mut inited = true;
-        if let None = self.handles.get_mut(category) {
-            inited = false;
+    /// Drops and joins every category whose name starts with `prefix`, e.g. "ui" tears down "ui.menu" and "ui.hud".
+    pub fn drop_join_pattern(&mut self, prefix: &str) {
+        let matching: Vec<String> = self.order.iter().rev().filter(|c| c.starts_with(prefix)).cloned().collect();
+        for category in matching.iter() {
+            self.drop_join_category(category);
+        }
+    }
+
+    /// Drops and joins every category from the highest teardown priority to the lowest.
+    /// Categories with the same priority are joined in the reverse order that they were first registered.
+    pub fn clear(&mut self) {
+        while let Some(category) = self.next_teardown() {
+            self.drop_join_category(&category);
+        }
+    }
+
+    /// Takes every handle out of the manager without joining any of them, leaving it empty. Timers created by
+    /// `schedule` are cancelled. The workers keep running until their handle's senders are dropped.
+    pub fn into_handles(mut self) -> HashMap<String, Vec<ThreadHandle>> {
+        let ids: Vec<u64> = self.schedules.keys().copied().collect();
+        for id in ids {
+            self.cancel_schedule(id);
         }
-        if !inited {
+        self.order.clear();
+        self.aggregators.clear();
+        mem::take(&mut self.handles)
+    }
+
+    fn lazy_init_category(&mut self, category: &str) {
+        if !self.handles.contains_key(category) {
+            self.exit_reasons.remove(category);
             self.handles.insert(String::from(category), Vec::new());
+            self.order.push(String::from(category));
         }
     }
 
-    fn static_system_create<'a: 'static, T: Any + Send + Sync>(&self, data: &'a T, f: fn(Arc<&'a T>)) -> ThreadHandle {
-        let (sx, rx): (SyncSender<()>, Receiver<()>) = mpsc::sync_channel(60);
-        let arc_data = Arc::new(data);
-        let handle = thread::spawn(move || {
+    fn handle(&self, category: &str, index: usize) -> Result<&ThreadHandle, EcstaticError> {
+        self.handles.get(category)
+            .ok_or_else(|| EcstaticError::UnknownCategory(String::from(category)))?
+            .get(index)
+            .ok_or_else(|| EcstaticError::UnknownHandle { category: String::from(category), index })
+    }
+
+    fn push_handle(&mut self, category: &str, th: ThreadHandle) {
+        self.lazy_init_category(category);
+        let ths = self.handles.get_mut(category).unwrap();
+        th.state.label(category, ths.len());
+        ths.push(th);
+    }
+
+    fn static_system_create<'a: 'static, T: Any + Send + Sync>(&self, category: &str, data: &'a T, f: fn(Arc<&'a T>)) -> ThreadHandle {
+        let data = data_slot(data);
+        let worker_data = data.clone();
+        let mut th = self.system_create(category, move || f(worker_data.load_full()));
+        let respawn_data = data.clone();
+        th.respawn = Some(Arc::new(move |sys: &EcstaticSystems, category: &str| sys.static_system_create(category, *respawn_data.load_full(), f)));
+        th.data = Some(data);
+        th
+    }
+
+    /// Spawns a thread which calls `run` once for every signal it receives, until its sender is dropped.
+    fn system_create<F: FnMut() + Send + 'static>(&self, category: &str, mut run: F) -> ThreadHandle {
+        self.system_create_with(category, || (), move |_| {
+            run();
+            true
+        }, |_| ())
+    }
+
+    /// Like `system_create`, but `init` creates state on the worker thread before it receives any signals,
+    /// `run` is given that state for every signal, and `exit` consumes it once the sender is dropped
+    /// or `run` returns false. `init` runs again whenever the category's `PanicPolicy` restarts the worker,
+    /// and before the first signal a disabled worker runs after it is enabled.
+    fn system_create_with<S, I, F, E>(&self, category: &str, init: I, run: F, exit: E) -> ThreadHandle
+    where
+        I: FnMut() -> S + Send + 'static,
+        F: FnMut(&mut S) -> bool + Send + 'static,
+        E: FnOnce(S) + Send + 'static,
+    {
+        self.system_create_reporting(category, self.reporter(category), init, run, exit)
+    }
+
+    /// Like `system_create_with`, but panics are reported through `reporter`, so `run` can report through a clone of it.
+    fn system_create_reporting<S, I, F, E>(&self, category: &str, reporter: sink::Reporter, mut init: I, mut run: F, exit: E) -> ThreadHandle
+    where
+        I: FnMut() -> S + Send + 'static,
+        F: FnMut(&mut S) -> bool + Send + 'static,
+        E: FnOnce(S) + Send + 'static,
+    {
+        let (sx, mut rx, capacity) = channel::signal_channel(self.channel_kind(category));
+        let state = Arc::new(SystemState::identified(reporter.id()));
+        let worker_state = state.clone();
+        let next_rx = resize::NextReceivers::default();
+        let worker_next_rx = next_rx.clone();
+        let ticks = barrier::TickSlot::default();
+        let worker_ticks = ticks.clone();
+        let lifecycle = lifecycle::Lifecycle::default();
+        let worker_lifecycle = lifecycle.clone();
+        let policy = self.panic_policy(category);
+        let handle = self.spawn_thread(category, move || {
+            let _closing = barrier::CloseOnExit(worker_ticks.clone());
+            let _stopping = lifecycle::StopOnExit(worker_lifecycle.clone());
+            let mut s = init();
+            worker_lifecycle.start();
+            let mut stale = false;
+            let mut unsampled = 0;
             loop {
-                match rx.recv() {
-                    Ok(_) => f(arc_data.clone()),
-                    Err(_) => break,
+                //a disconnected receiver was either dropped or replaced by `resize_buffer`.
+                if rx.recv().is_err() {
+                    match worker_next_rx.lock().unwrap().pop_front() {
+                        Some(next) => {
+                            rx = next;
+                            continue;
+                        }
+                        None => break,
+                    }
+                }
+                worker_state.received();
+                worker_lifecycle.start();
+                let _tick = worker_ticks.join();
+                if worker_state.is_disabled() {
+                    worker_state.skip();
+                    continue;
+                }
+                unsampled += 1;
+                if unsampled < worker_state.sample_rate() {
+                    worker_state.skip();
+                    continue;
+                }
+                unsampled = 0;
+                if stale {
+                    s = init();
+                    stale = false;
+                }
+                let start = Instant::now();
+                match panic::catch_unwind(AssertUnwindSafe(|| run(&mut s))) {
+                    Ok(keep_running) => {
+                        worker_state.complete(start.elapsed());
+                        if !keep_running {
+                            exit(s);
+                            worker_state.exited(ExitReason::SelfTerminated);
+                            return;
+                        }
+                    }
+                    Err(payload) => {
+                        reporter.panicked(payload.as_ref());
+                        worker_state.panicked();
+                        match policy {
+                            PanicPolicy::Exit => {
+                                worker_state.exited(ExitReason::Panicked);
+                                return;
+                            }
+                            PanicPolicy::Restart(drain) => {
+                                if drain == RestartDrain::DiscardBuffered {
+                                    while rx.try_recv().is_ok() {
+                                        worker_state.received();
+                                        mem::drop(worker_ticks.join());
+                                        worker_state.skip();
+                                    }
+                                }
+                                worker_state.complete(start.elapsed());
+                                s = init();
+                            }
+                            PanicPolicy::Disable => {
+                                worker_state.complete(start.elapsed());
+                                worker_state.disable();
+                                stale = true;
+                            }
+                        }
+                    }
                 }
             }
+            exit(s);
+            worker_state.exited(ExitReason::Normal);
         });
         ThreadHandle {
             sx: Some(sx),
+            thread_id: Some(handle.thread().id()),
             join_handle: Some(handle),
+            state,
+            capacity,
+            next_rx: Some(next_rx),
+            ticks: Some(ticks),
+            lifecycle: Some(lifecycle),
+            ..Default::default()
+        }
+    }
+
+    /// Spawns a worker thread for a category using the category's configuration, or through the manager's spawner if it has one.
+    fn spawn_thread<F: FnOnce() + Send + 'static>(&self, category: &str, f: F) -> JoinHandle<()> {
+        #[cfg(feature = "core_affinity")]
+        let f = {
+            let core = self.next_core(category);
+            move || {
+                if let Some(core) = core {
+                    affinity::pin_current(core);
+                }
+                f()
+            }
+        };
+        self.spawned.fetch_add(1, Ordering::Relaxed);
+        if let Some(spawner) = self.spawner.as_ref() {
+            return spawner(Box::new(f));
+        }
+        let mut builder = thread::Builder::new();
+        if let Some(bytes) = self.configs.get(category).and_then(|config| config.stack_size) {
+            builder = builder.stack_size(bytes);
         }
+        builder.spawn(f).expect("failed to spawn system thread")
+    }
+}
+
+/// Drops the senders for every handle and joins their threads in reverse order, returning why each one stopped.
+fn join_handles(ths: &mut [ThreadHandle], joined: &AtomicUsize) -> Vec<Option<ExitReason>> {
+    for token in ths.iter().filter_map(|th| th.cancel.as_ref()) {
+        token.cancel();
+    }
+    //message senders can be shared by several workers, so every one is dropped before joining any of them.
+    for th in ths.iter_mut() {
+        mem::drop(th.mx.take());
+    }
+    let mut reasons = Vec::with_capacity(ths.len());
+    for th in ths.iter_mut().rev() {
+        let mut handle = mem::take(th);
+        mem::drop(handle.sx.take());
+        reasons.push(handle.join_handle.take().and_then(|join_handle| {
+            let result = join_handle.join();
+            joined.fetch_add(1, Ordering::Relaxed);
+            match result {
+                Ok(()) => handle.state.exit_reason(),
+                Err(_) => Some(ExitReason::Aborted),
+            }
+        }));
+    }
+    reasons.reverse();
+    reasons
+}
+
+impl Drop for EcstaticSystems {
+    /// Joins every remaining system, see `clear`. Debug builds report systems which were still running, see `ErrorEvent::Leaked`.
+    fn drop(&mut self) {
+        #[cfg(debug_assertions)]
+        self.report_leaked();
+        self.clear();
     }
 }
 
 #[cfg(test)]
 mod tests {
     use std::sync::atomic::{AtomicUsize, Ordering};
-    use std::sync::mpsc;
-    use std::sync::mpsc::{SyncSender, Receiver, RecvError};
+    use std::sync::{Arc, Mutex};
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::thread::{self, ThreadId};
+    use std::time::Duration;
     use std::mem;
 
-    use super::EcstaticSystems;
+    use super::{EcstaticError, EcstaticSystems};
+    use crate::test_util::assert_invocations;
+
+    struct JoinRecorder {
+        id: usize,
+        order: &'static Mutex<Vec<usize>>,
+    }
+
+    //pushes its id when the worker thread's thread locals are destroyed, which happens just before it is joined.
+    struct OnExit(usize, &'static Mutex<Vec<usize>>);
+
+    impl Drop for OnExit {
+        fn drop(&mut self) {
+            self.1.lock().unwrap().push(self.0);
+        }
+    }
+
+    thread_local! {
+        static ON_EXIT: RefCell<Option<OnExit>> = const { RefCell::new(None) };
+    }
+
+    fn record_on_exit(r: Arc<&JoinRecorder>) {
+        ON_EXIT.with(|e| *e.borrow_mut() = Some(OnExit(r.id, r.order)));
+    }
 
     #[test]
     fn test_register_system_and_signals() {
@@ -182,15 +729,213 @@ mod tests {
         let sys = EcstaticSystems::new();
         static ATOMIC: AtomicUsize = AtomicUsize::new(0);
 
-        let th = sys.static_system_create(&ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        let th = sys.static_system_create("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
         assert!(ATOMIC.load(Ordering::Relaxed) == 0);
-        for i in 0..100 {
-            th.sx.as_ref().unwrap().send(());
+        for _ in 0..100 {
+            th.sx.as_ref().unwrap().send(()).unwrap();
         }
 
         mem::drop(th.sx);               //drop the original sender to send the signal to terminate the thread.         
-        th.join_handle.unwrap().join(); //wait for thread to finish the buffered work in the channel. thread is cleaned up afterwards.
+        th.join_handle.unwrap().join().unwrap(); //wait for thread to finish the buffered work in the channel. thread is cleaned up afterwards.
 
         assert!(ATOMIC.load(Ordering::Relaxed) == 100, "Actual: {} ; Expected: {}", ATOMIC.load(Ordering::Relaxed), 100);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_clear_joins_in_reverse_registration_order() {
+        let mut sys = EcstaticSystems::new();
+        static ORDER: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+        static R0: JoinRecorder = JoinRecorder { id: 0, order: &ORDER };
+        static R1: JoinRecorder = JoinRecorder { id: 1, order: &ORDER };
+        static R2: JoinRecorder = JoinRecorder { id: 2, order: &ORDER };
+        static R3: JoinRecorder = JoinRecorder { id: 3, order: &ORDER };
+
+        sys.register_static("a", &R0, record_on_exit);
+        sys.register_static("a", &R1, record_on_exit);
+        sys.register_static("b", &R2, record_on_exit);
+        sys.register_static("c", &R3, record_on_exit);
+        sys.signal_all();
+        sys.clear();
+
+        assert!(*ORDER.lock().unwrap() == vec![3, 2, 1, 0], "Actual: {:?}", *ORDER.lock().unwrap());
+        assert!(sys.handles.is_empty());
+    }
+
+    #[test]
+    fn test_drop_joins_in_reverse_registration_order() {
+        static ORDER: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+        static R0: JoinRecorder = JoinRecorder { id: 0, order: &ORDER };
+        static R1: JoinRecorder = JoinRecorder { id: 1, order: &ORDER };
+        static R2: JoinRecorder = JoinRecorder { id: 2, order: &ORDER };
+
+        {
+            let mut sys = EcstaticSystems::new();
+            sys.register_static("b", &R0, record_on_exit);
+            sys.register_static("a", &R1, record_on_exit);
+            sys.register_static("b", &R2, record_on_exit);
+            sys.signal_all();
+        }
+
+        assert!(*ORDER.lock().unwrap() == vec![1, 2, 0], "Actual: {:?}", *ORDER.lock().unwrap());
+    }
+
+    #[test]
+    fn test_drop_join_pattern() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("ui.a", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("ui.b", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("net.x", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.signal_all();
+        sys.drop_join_pattern("ui");
+
+        assert!(sys.handles.len() == 1 && sys.handles.contains_key("net.x"));
+        assert!(sys.order == vec![String::from("net.x")]);
+        sys.drop_join_category("net.x");
+        assert!(ATOMIC.load(Ordering::Relaxed) == 3);
+    }
+
+    #[test]
+    fn test_register_static_once() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        assert!(sys.register_static_once("testing", "counter", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); }));
+        assert!(!sys.register_static_once("testing", "counter", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); }));
+        assert!(sys.handles.get("testing").unwrap().len() == 1);
+
+        sys.signal("testing");
+        sys.drop_join_category("testing");
+        assert!(ATOMIC.load(Ordering::Relaxed) == 1);
+    }
+
+    struct ThreadRecorder {
+        id: usize,
+        seen: &'static Mutex<Option<HashMap<usize, ThreadId>>>,
+    }
+
+    #[test]
+    fn test_thread_id() {
+        let mut sys = EcstaticSystems::new();
+        static SEEN: Mutex<Option<HashMap<usize, ThreadId>>> = Mutex::new(None);
+        static R0: ThreadRecorder = ThreadRecorder { id: 0, seen: &SEEN };
+        static R1: ThreadRecorder = ThreadRecorder { id: 1, seen: &SEEN };
+
+        let record: fn(Arc<&ThreadRecorder>) = |r| {
+            r.seen.lock().unwrap().get_or_insert_with(HashMap::new).insert(r.id, thread::current().id());
+        };
+        sys.register_static("testing", &R0, record);
+        sys.register_static("testing", &R1, record);
+        sys.step(Duration::from_secs(5));
+
+        let seen = SEEN.lock().unwrap().clone().unwrap();
+        assert!(sys.thread_id("testing", 0) == Some(seen[&0]));
+        assert!(sys.thread_id("testing", 1) == Some(seen[&1]));
+        assert!(seen[&0] != seen[&1]);
+        assert!(sys.thread_id("testing", 2).is_none());
+        sys.drop_join_category("testing");
+    }
+
+    #[test]
+    fn test_has_category() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        assert!(!sys.has_category("testing"));
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        assert!(sys.has_category("testing"));
+        sys.drop_join_category("testing");
+        assert!(!sys.has_category("testing"));
+    }
+
+    #[test]
+    fn test_handles() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        assert!(sys.handles("testing").is_none());
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static_once("testing", "second", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        let ths = sys.handles("testing").unwrap();
+        assert!(ths.len() == 2, "Actual: {:?}", ths.len());
+        assert!(ths[1].key.as_deref() == Some("second"));
+        assert!(ths.iter().map(|th| th.thread_id).collect::<Vec<_>>() == vec![sys.thread_id("testing", 0), sys.thread_id("testing", 1)]);
+        sys.drop_join_category("testing");
+        assert!(sys.handles("testing").is_none());
+    }
+
+    #[test]
+    fn test_into_handles() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("a", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("a", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("b", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.schedule("b", Duration::from_millis(1)).unwrap();
+        sys.signal_all();
+        let mut handles = sys.into_handles();
+
+        let mut positions: Vec<(String, usize)> = handles.iter()
+            .flat_map(|(category, ths)| ths.iter().enumerate().map(move |(index, th)| {
+                assert!(th.category() == *category && th.index() == index, "Actual: {:?} ; Expected: {:?}", (th.category(), th.index()), (category, index));
+                (th.category(), th.index())
+            }))
+            .collect();
+        positions.sort();
+        assert!(positions == vec![(String::from("a"), 0), (String::from("a"), 1), (String::from("b"), 0)], "Actual: {:?}", positions);
+        for th in handles.values_mut().flatten() {
+            mem::drop(th.sx.take());
+            th.join_handle.take().unwrap().join().unwrap();
+        }
+        assert!(ATOMIC.load(Ordering::SeqCst) >= 3);
+    }
+
+    #[test]
+    fn test_create_category() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.create_category("testing").unwrap();
+        assert!(sys.create_category("testing") == Err(EcstaticError::CategoryExists(String::from("testing"))));
+        assert!(sys.has_category("testing"));
+        sys.signal("testing");
+        sys.signal_all();
+
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.signal("testing");
+        assert_invocations(&sys, "testing", 0, 1);
+        sys.drop_join_category("testing");
+        assert!(ATOMIC.load(Ordering::Relaxed) == 1);
+        assert!(sys.create_category("testing").is_ok());
+        sys.clear();
+    }
+
+    #[test]
+    fn test_signal_all_many_categories() {
+        let mut sys = EcstaticSystems::new();
+        static COUNTS: [AtomicUsize; 64] = [const { AtomicUsize::new(0) }; 64];
+
+        for (i, count) in COUNTS.iter().enumerate() {
+            sys.register_static(&format!("c{}", i), count, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        }
+        sys.start_recording();
+        sys.signal_all();
+        sys.signal_all();
+        let log = sys.stop_recording();
+        for i in 0..COUNTS.len() {
+            assert_invocations(&sys, &format!("c{}", i), 0, 2);
+        }
+
+        //every category is visited once per call, so it is recorded once per call.
+        let mut visits: HashMap<&str, usize> = HashMap::new();
+        for record in log.records.iter() {
+            *visits.entry(record.category.as_str()).or_default() += 1;
+        }
+        assert!(log.records.len() == 2 * COUNTS.len(), "Actual: {:?}", log.records.len());
+        assert!(visits.values().all(|visits| *visits == 2), "Actual: {:?}", visits);
+        sys.clear();
+        assert!(COUNTS.iter().all(|count| count.load(Ordering::SeqCst) == 2));
+    }
+}
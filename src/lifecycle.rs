@@ -0,0 +1,118 @@
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+use super::{EcstaticError, EcstaticSystems};
+
+type Callback = Box<dyn FnOnce() + Send>;
+
+#[derive(Default)]
+struct Hooks {
+    stopped: bool,
+    on_started: Vec<Callback>,
+    on_stopped: Vec<Callback>,
+}
+
+/// Start and stop callbacks of a worker, shared between its handle and its worker thread.
+#[derive(Clone, Default)]
+pub(crate) struct Lifecycle(Arc<Mutex<Hooks>>);
+
+impl Lifecycle {
+    /// Runs every started callback registered so far. Called by the worker once its state is set up,
+    /// and again before each signal to catch callbacks registered after that.
+    pub(crate) fn start(&self) {
+        let callbacks = mem::take(&mut self.0.lock().unwrap().on_started);
+        for cb in callbacks {
+            cb();
+        }
+    }
+
+    fn stop(&self) {
+        let callbacks = {
+            let mut hooks = self.0.lock().unwrap();
+            hooks.stopped = true;
+            hooks.on_started.clear();
+            mem::take(&mut hooks.on_stopped)
+        };
+        for cb in callbacks {
+            cb();
+        }
+    }
+}
+
+/// Runs a worker's stopped callbacks when its thread stops, however it stops.
+pub(crate) struct StopOnExit(pub(crate) Lifecycle);
+
+impl Drop for StopOnExit {
+    fn drop(&mut self) {
+        self.0.stop();
+    }
+}
+
+impl EcstaticSystems {
+    fn lifecycle(&self, category: &str, index: usize) -> Result<&Lifecycle, EcstaticError> {
+        self.handle(category, index)?
+            .lifecycle
+            .as_ref()
+            .ok_or_else(|| EcstaticError::Unsupported { category: String::from(category), index })
+    }
+
+    /// Calls `cb` on a handle's worker thread once the worker has set up its state, before it runs any signal.
+    /// If the worker has already started, `cb` runs before the next signal it receives, and never if it has stopped.
+    pub fn on_system_started<F: FnOnce() + Send + 'static>(&self, category: &str, index: usize, cb: F) -> Result<(), EcstaticError> {
+        let mut hooks = self.lifecycle(category, index)?.0.lock().unwrap();
+        if !hooks.stopped {
+            hooks.on_started.push(Box::new(cb));
+        }
+        Ok(())
+    }
+
+    /// Calls `cb` on a handle's worker thread when it stops for any reason, or right away on the calling thread
+    /// if it has already stopped.
+    pub fn on_system_stopped<F: FnOnce() + Send + 'static>(&self, category: &str, index: usize, cb: F) -> Result<(), EcstaticError> {
+        let mut hooks = self.lifecycle(category, index)?.0.lock().unwrap();
+        if hooks.stopped {
+            mem::drop(hooks);
+            cb();
+        } else {
+            hooks.on_stopped.push(Box::new(cb));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread::{self, ThreadId};
+
+    use crate::test_util::assert_invocations;
+    use crate::{EcstaticError, EcstaticSystems};
+
+    static EVENTS: Mutex<Vec<(&str, ThreadId)>> = Mutex::new(Vec::new());
+
+    fn event(name: &'static str) {
+        EVENTS.lock().unwrap().push((name, thread::current().id()));
+    }
+
+    #[test]
+    fn test_started_and_stopped_callbacks() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("testing", &ATOMIC, |_: Arc<&AtomicUsize>| event("run"));
+        sys.register_inline("inline", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.on_system_started("testing", 0, || event("started")).unwrap();
+        sys.on_system_stopped("testing", 0, || event("stopped")).unwrap();
+        assert!(sys.on_system_started("inline", 0, || ()) == Err(EcstaticError::Unsupported { category: String::from("inline"), index: 0 }));
+        let worker = sys.thread_id("testing", 0).unwrap();
+
+        sys.signal("testing");
+        assert_invocations(&sys, "testing", 0, 1);
+        sys.drop_join_category("testing");
+
+        let events = EVENTS.lock().unwrap().clone();
+        assert!(events == vec![("started", worker), ("run", worker), ("stopped", worker)], "Actual: {:?}", events);
+        sys.clear();
+    }
+}
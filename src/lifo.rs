@@ -0,0 +1,112 @@
+use std::any::Any;
+use std::marker::{Send, Sync};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::time::Instant;
+
+use super::channel::signal_channel;
+use super::{data_slot, EcstaticSystems, ExitReason, SystemState, ThreadHandle};
+
+impl EcstaticSystems {
+    /// Registers a system like `register_static` which handles its buffered signals newest first, for systems where
+    /// only the latest request matters. `f` is called with the tick id of the signal it handles, which counts signals
+    /// from 0 in the order the worker took them out of its buffer.
+    ///
+    /// Before every invocation the worker moves all buffered signals onto a stack and runs the most recent one,
+    /// so older signals only run once no newer one is waiting. A panic makes the worker exit.
+    pub fn register_lifo<'a: 'static, T: Any + Send + Sync>(&mut self, category: &str, data: &'a T, f: fn(Arc<&'a T>, u64)) {
+        if self.is_shutting_down() {
+            return;
+        }
+        let (sx, rx, capacity) = signal_channel(self.channel_kind(category));
+        let data = data_slot(data);
+        let worker_data = data.clone();
+        let reporter = self.reporter(category);
+        let state = Arc::new(SystemState::identified(reporter.id()));
+        let worker_state = state.clone();
+        let handle = self.spawn_thread(category, move || {
+            let mut stack = Vec::new();
+            let mut next_id = 0;
+            loop {
+                if stack.is_empty() {
+                    if rx.recv().is_err() {
+                        break;
+                    }
+                    worker_state.received();
+                    stack.push(next_id);
+                    next_id += 1;
+                }
+                while rx.try_recv().is_ok() {
+                    worker_state.received();
+                    stack.push(next_id);
+                    next_id += 1;
+                }
+                let id = stack.pop().unwrap();
+                if worker_state.is_disabled() {
+                    worker_state.skip();
+                    continue;
+                }
+                let start = Instant::now();
+                if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| f(worker_data.load_full(), id))) {
+                    reporter.panicked(payload.as_ref());
+                    worker_state.panicked();
+                    worker_state.exited(ExitReason::Panicked);
+                    return;
+                }
+                worker_state.complete(start.elapsed());
+            }
+            worker_state.exited(ExitReason::Normal);
+        });
+        let th = ThreadHandle {
+            sx: Some(sx),
+            thread_id: Some(handle.thread().id()),
+            join_handle: Some(handle),
+            state,
+            capacity,
+            data: Some(data),
+            ..Default::default()
+        };
+        self.push_handle(category, th);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    use crate::test_util::{assert_invocations, pump_until};
+    use crate::EcstaticSystems;
+
+    struct Redraw {
+        released: AtomicBool,
+        ticks: Mutex<Vec<u64>>,
+    }
+
+    fn redraw(x: Arc<&Redraw>, tick: u64) {
+        x.ticks.lock().unwrap().push(tick);
+        while !x.released.load(Ordering::SeqCst) {
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn test_register_lifo() {
+        let mut sys = EcstaticSystems::new();
+        static REDRAW: Redraw = Redraw { released: AtomicBool::new(false), ticks: Mutex::new(Vec::new()) };
+
+        sys.register_lifo("testing", &REDRAW, redraw);
+        sys.signal("testing");
+        pump_until(&sys, |_| REDRAW.ticks.lock().unwrap().len() == 1);
+        for _ in 0..4 {
+            sys.signal("testing");
+        }
+        REDRAW.released.store(true, Ordering::SeqCst);
+        assert_invocations(&sys, "testing", 0, 5);
+
+        let ticks = REDRAW.ticks.lock().unwrap().clone();
+        assert!(ticks == vec![0, 4, 3, 2, 1], "Actual: {:?}", ticks);
+        sys.clear();
+    }
+}
@@ -0,0 +1,93 @@
+use std::any::Any;
+use std::marker::{Send, Sync};
+use std::sync::Arc;
+use std::thread;
+
+use super::EcstaticSystems;
+
+impl EcstaticSystems {
+    /// Registers a system which only runs on the thread that created the manager, for work such as GUI or GL calls.
+    /// Signals are buffered as usual and run when that thread calls `pump_main`. `poll_once` does not run them.
+    /// Signals sent while the buffer is full are dropped and reported as `ErrorEvent::SignalDropped`, rather than blocking.
+    pub fn register_main_thread<'a: 'static, T: Any + Send + Sync>(&mut self, category: &str, data: &'a T, f: fn(Arc<&'a T>)) {
+        if self.is_shutting_down() {
+            return;
+        }
+        let mut th = self.inline_system_create(category, data, f);
+        th.main = th.inline.take();
+        th.thread_id = Some(self.main_thread);
+        self.push_handle(category, th);
+    }
+
+    /// Runs every buffered signal of every main thread system without blocking. Returns how many invocations ran.
+    /// Panics if called from any thread other than the one which created the manager.
+    pub fn pump_main(&self) -> usize {
+        assert!(thread::current().id() == self.main_thread, "pump_main must be called from the thread which created the manager");
+        let mut ran = 0;
+        for runner in self.order.iter().flat_map(|category| self.handles[category].iter()).filter_map(|th| th.main.as_ref()) {
+            let mut runner = runner.lock().unwrap();
+            while runner() {
+                ran += 1;
+            }
+        }
+        ran
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread::{self, ThreadId};
+
+    use crate::test_util::assert_invocations;
+    use crate::{CategoryConfig, CollectingSink, EcstaticSystems, ErrorEvent};
+
+    static RAN_ON: Mutex<Vec<ThreadId>> = Mutex::new(Vec::new());
+
+    fn record(_: Arc<&AtomicUsize>) {
+        RAN_ON.lock().unwrap().push(thread::current().id());
+    }
+
+    #[test]
+    fn test_pump_main() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_main_thread("main", &ATOMIC, record);
+        sys.register_static("worker", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.signal_all();
+        sys.signal_all();
+        assert_invocations(&sys, "worker", 0, 2);
+        assert!(RAN_ON.lock().unwrap().is_empty());
+        assert!(sys.poll_once() == 0);
+
+        assert!(sys.pump_main() == 2);
+        assert!(sys.pump_main() == 0);
+        let ran_on = RAN_ON.lock().unwrap().clone();
+        assert!(ran_on == vec![thread::current().id(); 2], "Actual: {:?}", ran_on);
+        assert!(sys.thread_id("main", 0) == Some(thread::current().id()));
+        sys.clear();
+    }
+
+    #[test]
+    fn test_main_thread_full_buffer_drops() {
+        let sink = CollectingSink::new();
+        let mut sys = EcstaticSystems::builder().error_sink(sink.clone()).build();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.configure_category("main", CategoryConfig { buffer_size: Some(3), ..Default::default() });
+        sys.register_main_thread("main", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        for _ in 0..5 {
+            sys.signal("main");
+        }
+        let events = sink.take();
+        assert!(events == vec![ErrorEvent::SignalDropped { category: String::from("main"), index: 0 }; 2], "Actual: {:?}", events);
+
+        assert!(sys.pump_main() == 3);
+        sys.signal("main");
+        assert!(sys.pump_main() == 1);
+        assert!(ATOMIC.load(Ordering::SeqCst) == 4, "Actual: {:?}", ATOMIC.load(Ordering::SeqCst));
+        sys.clear();
+    }
+}
@@ -0,0 +1,130 @@
+use std::mem;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Weak};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use super::{EcstaticSystems, SystemState};
+
+/// How often a slow consumer monitor samples queue depths.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// A handle whose buffer stayed above the threshold given to `on_slow_consumer`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlowConsumer {
+    pub category: String,
+    pub index: usize,
+    /// How many signals were buffered when the callback fired.
+    pub depth: usize,
+    /// How long the depth had been above the threshold when the callback fired.
+    pub sustained: Duration,
+}
+
+struct Watched {
+    category: String,
+    index: usize,
+    state: Weak<SystemState>,
+    above_since: Option<Instant>,
+    fired: bool,
+}
+
+/// A running monitor thread. It is stopped and joined once dropped.
+pub(crate) struct Monitor {
+    stop: Option<Sender<()>>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl Drop for Monitor {
+    fn drop(&mut self) {
+        mem::drop(self.stop.take());
+        if let Some(join_handle) = self.join_handle.take() {
+            let _ = join_handle.join();
+        }
+    }
+}
+
+impl EcstaticSystems {
+    /// Calls `cb` from a monitor thread whenever a handle's buffer holds more than `threshold` signals for at least
+    /// `duration`. The callback fires once per episode and again only after the depth has dropped back to the threshold.
+    /// Watches every signaled handle registered when this is called, until its category is dropped.
+    pub fn on_slow_consumer<F: Fn(&SlowConsumer) + Send + 'static>(&mut self, threshold: usize, duration: Duration, cb: F) {
+        let mut watched: Vec<Watched> = self.order.iter()
+            .flat_map(|category| self.handles[category].iter().enumerate().map(move |(index, th)| (category, index, th)))
+            .filter(|(_, _, th)| th.sx.is_some())
+            .map(|(category, index, th)| Watched {
+                category: category.clone(),
+                index,
+                state: Arc::downgrade(&th.state),
+                above_since: None,
+                fired: false,
+            })
+            .collect();
+        let (stop, stopped) = mpsc::channel::<()>();
+        let join_handle = self.spawn_helper(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(POLL_INTERVAL) {
+                let now = Instant::now();
+                for w in watched.iter_mut() {
+                    let depth = match w.state.upgrade() {
+                        Some(state) => state.pending(),
+                        None => continue,
+                    };
+                    if depth <= threshold {
+                        w.above_since = None;
+                        w.fired = false;
+                        continue;
+                    }
+                    let since = *w.above_since.get_or_insert(now);
+                    if !w.fired && now - since >= duration {
+                        w.fired = true;
+                        cb(&SlowConsumer { category: w.category.clone(), index: w.index, depth, sustained: now - since });
+                    }
+                }
+            }
+        });
+        self.monitors.push(Monitor { stop: Some(stop), join_handle: Some(join_handle) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use super::SlowConsumer;
+    use crate::test_util::{gated, pump_until, Gated};
+    use crate::{CategoryConfig, EcstaticSystems};
+
+    #[test]
+    fn test_on_slow_consumer() {
+        let mut sys = EcstaticSystems::new();
+        static GATED: Gated = Gated::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        static SLOW: Mutex<Vec<SlowConsumer>> = Mutex::new(Vec::new());
+        let duration = Duration::from_millis(50);
+
+        sys.configure_category("wedged", CategoryConfig { buffer_size: Some(10), ..Default::default() });
+        sys.register_static("fine", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("wedged", &GATED, gated);
+        sys.on_slow_consumer(3, duration, |slow| SLOW.lock().unwrap().push(slow.clone()));
+        sys.signal("wedged");
+        pump_until(&sys, |_| GATED.runs.load(Ordering::SeqCst) == 1);
+        for _ in 0..5 {
+            sys.signal_all();
+        }
+        let filled = Instant::now();
+
+        pump_until(&sys, |_| !SLOW.lock().unwrap().is_empty());
+        assert!(filled.elapsed() >= duration);
+        thread::sleep(duration * 2);
+        let slow = SLOW.lock().unwrap().clone();
+        assert!(slow.len() == 1, "Actual: {:?}", slow);
+        assert!(slow[0].category == "wedged" && slow[0].index == 0 && slow[0].depth == 5, "Actual: {:?}", slow);
+        assert!(slow[0].sustained >= duration);
+
+        GATED.released.store(true, Ordering::SeqCst);
+        sys.clear();
+        assert!(ATOMIC.load(Ordering::SeqCst) == 5);
+    }
+}
@@ -0,0 +1,136 @@
+use super::{EcstaticError, EcstaticSystems};
+
+/// What a signaled worker does after its system panics. Every panic is reported to the error sink either way.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PanicPolicy {
+    /// The worker thread exits and later signals to it are dropped.
+    #[default]
+    Exit,
+    /// The worker recreates its state and keeps receiving signals.
+    Restart(RestartDrain),
+    /// The worker stays alive but drops every signal until `enable` is called on its handle,
+    /// then recreates its state before running again. Dropped signals still count as completed for anything waiting on them.
+    Disable,
+}
+
+/// Whether a restarted worker runs the signals which were buffered when its system panicked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RestartDrain {
+    /// Buffered signals run after the restart as if nothing happened.
+    KeepBuffered,
+    /// Buffered signals are dropped during the restart without running, but still count as completed.
+    DiscardBuffered,
+}
+
+impl EcstaticSystems {
+    /// Sets the panic policy of workers spawned into a category from now on. Categories use `PanicPolicy::Exit` until assigned.
+    pub fn set_panic_policy(&mut self, category: &str, policy: PanicPolicy) {
+        self.configs.entry(String::from(category)).or_default().panic_policy = policy;
+    }
+
+    pub(crate) fn panic_policy(&self, category: &str) -> PanicPolicy {
+        self.configs.get(category).map(|config| config.panic_policy).unwrap_or_default()
+    }
+
+    /// Lets a handle disabled by `PanicPolicy::Disable` run its system again. Signals sent while it was
+    /// disabled stay dropped. Does nothing to handles which are not disabled.
+    pub fn enable(&self, category: &str, index: usize) -> Result<(), EcstaticError> {
+        self.handle(category, index)?.state.enable();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    use super::{PanicPolicy, RestartDrain};
+    use crate::test_util::{assert_invocations, pump_until, DEFAULT_TIMEOUT};
+    use crate::{EcstaticSystems, HandleStatus};
+
+    struct Gate {
+        released: AtomicBool,
+        runs: AtomicUsize,
+    }
+
+    fn panic_first(gate: Arc<&Gate>) {
+        if gate.runs.fetch_add(1, Ordering::SeqCst) == 0 {
+            while !gate.released.load(Ordering::SeqCst) {
+                thread::yield_now();
+            }
+            panic!("first run");
+        }
+    }
+
+    fn runs_after_restart(gate: &'static Gate, drain: RestartDrain) -> usize {
+        let mut sys = EcstaticSystems::new();
+        sys.set_panic_policy("testing", PanicPolicy::Restart(drain));
+        sys.register_static("testing", gate, panic_first);
+        for _ in 0..4 {
+            sys.signal("testing");
+        }
+        pump_until(&sys, |_| gate.runs.load(Ordering::SeqCst) > 0);
+        gate.released.store(true, Ordering::SeqCst);
+        pump_until(&sys, |sys| sys.stats("testing", 0).unwrap().count > 0);
+        sys.signal("testing");
+        pump_until(&sys, |sys| sys.stats("testing", 0).unwrap().count > 1);
+        sys.wait_category_idle("testing", DEFAULT_TIMEOUT).unwrap();
+        sys.drop_join_category("testing");
+        gate.runs.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn test_restart_keeps_buffered() {
+        static GATE: Gate = Gate { released: AtomicBool::new(false), runs: AtomicUsize::new(0) };
+        let runs = runs_after_restart(&GATE, RestartDrain::KeepBuffered);
+        assert!(runs == 5, "Actual: {:?}", runs);
+    }
+
+    #[test]
+    fn test_restart_discards_buffered() {
+        static GATE: Gate = Gate { released: AtomicBool::new(false), runs: AtomicUsize::new(0) };
+        let runs = runs_after_restart(&GATE, RestartDrain::DiscardBuffered);
+        assert!(runs == 2, "Actual: {:?}", runs);
+    }
+
+    struct Armed {
+        armed: AtomicBool,
+        runs: AtomicUsize,
+    }
+
+    fn panic_once(x: Arc<&Armed>) {
+        if x.armed.swap(false, Ordering::SeqCst) {
+            panic!("armed");
+        }
+        x.runs.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn test_disable_and_enable() {
+        let mut sys = EcstaticSystems::new();
+        static ARMED: Armed = Armed { armed: AtomicBool::new(true), runs: AtomicUsize::new(0) };
+
+        sys.set_panic_policy("testing", PanicPolicy::Disable);
+        sys.register_static("testing", &ARMED, panic_once);
+        sys.signal("testing");
+        pump_until(&sys, |sys| sys.handle_status("testing", 0) == Ok(HandleStatus::Disabled));
+
+        for _ in 0..3 {
+            sys.signal("testing");
+        }
+        sys.signal_all_and_wait();
+        let acks = sys.signal_all_acked("testing");
+        assert!(acks[0].recv_timeout(DEFAULT_TIMEOUT) == Ok(()));
+        assert!(ARMED.runs.load(Ordering::SeqCst) == 0);
+        assert!(sys.exit_reason("testing", 0).is_none());
+
+        sys.enable("testing", 0).unwrap();
+        assert!(sys.handle_status("testing", 0) == Ok(HandleStatus::Running));
+        sys.signal("testing");
+        assert_invocations(&sys, "testing", 0, 2);
+        sys.drop_join_category("testing");
+        assert!(ARMED.runs.load(Ordering::SeqCst) == 1, "Actual: {:?}", ARMED.runs.load(Ordering::SeqCst));
+    }
+}
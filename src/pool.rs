@@ -0,0 +1,90 @@
+use std::any::Any;
+use std::marker::{Send, Sync};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use super::{data_slot, EcstaticError, EcstaticSystems, ExitReason, SystemState, ThreadHandle};
+
+impl EcstaticSystems {
+    /// Registers `n` workers which take jobs from one shared queue, so each job submitted through
+    /// `submit_job` runs exactly once on whichever worker is free. Pool workers are not signaled.
+    pub fn register_worker_pool<'a: 'static, T: Any + Send + Sync, Job: Any + Send>(&mut self, category: &str, data: &'a T, f: fn(Arc<&'a T>, Job), n: usize) {
+        if self.is_shutting_down() {
+            return;
+        }
+        let (mx, rx) = mpsc::sync_channel::<Job>(self.buffer_size(category));
+        let rx = Arc::new(Mutex::new(rx));
+        let data = data_slot(data);
+        for _ in 0..n {
+            let worker_rx = rx.clone();
+            let worker_data = data.clone();
+            let reporter = self.reporter(category);
+            let state = Arc::new(SystemState::identified(reporter.id()));
+            let worker_state = state.clone();
+            let handle = self.spawn_thread(category, move || loop {
+                let job = worker_rx.lock().unwrap().recv();
+                match job {
+                    Ok(job) => {
+                        worker_state.received();
+                        let start = Instant::now();
+                        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| f(worker_data.load_full(), job))) {
+                            reporter.panicked(payload.as_ref());
+                            worker_state.exited(ExitReason::Panicked);
+                            break;
+                        }
+                        worker_state.complete(start.elapsed());
+                    }
+                    Err(_) => {
+                        worker_state.exited(ExitReason::Normal);
+                        break;
+                    }
+                }
+            });
+            let th = ThreadHandle {
+                thread_id: Some(handle.thread().id()),
+                join_handle: Some(handle),
+                mx: Some(Box::new(mx.clone())),
+                state,
+                data: Some(data.clone()),
+                ..Default::default()
+            };
+            self.push_handle(category, th);
+        }
+    }
+
+    /// Queues a job for a worker pool, blocking while the pool's queue is full.
+    pub fn submit_job<Job: Any + Send>(&self, category: &str, job: Job) -> Result<(), EcstaticError> {
+        let ths = self.handles.get(category).ok_or_else(|| EcstaticError::UnknownCategory(String::from(category)))?;
+        let (index, mx) = ths.iter().enumerate()
+            .find_map(|(index, th)| th.mx.as_ref()?.downcast_ref::<SyncSender<Job>>().map(|mx| (index, mx)))
+            .ok_or_else(|| EcstaticError::TypeMismatch { category: String::from(category), index: 0 })?;
+        mx.send(job).map_err(|_| EcstaticError::Disconnected { category: String::from(category), index })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::{EcstaticError, EcstaticSystems};
+
+    #[test]
+    fn test_worker_pool() {
+        let mut sys = EcstaticSystems::new();
+        static DONE: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+
+        sys.register_worker_pool("pool", &DONE, |done, job: usize| { done.lock().unwrap().push(job); }, 4);
+        assert!(sys.handles.get("pool").unwrap().len() == 4);
+        for job in 0..20usize {
+            sys.submit_job("pool", job).unwrap();
+        }
+        assert!(sys.submit_job("pool", "wrong job") == Err(EcstaticError::TypeMismatch { category: String::from("pool"), index: 0 }));
+        sys.drop_join_category("pool");
+
+        let mut done = DONE.lock().unwrap().clone();
+        done.sort_unstable();
+        assert!(done == (0..20).collect::<Vec<_>>(), "Actual: {:?}", done);
+    }
+}
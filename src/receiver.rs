@@ -0,0 +1,148 @@
+use std::any::Any;
+use std::marker::{Send, Sync};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use super::{data_slot, EcstaticError, EcstaticSystems, ExitReason, SystemState, ThreadHandle};
+
+/// The send path of a receiver. Holding the lock keeps other producers from interleaving messages.
+type MessageSender<M> = Mutex<SyncSender<M>>;
+
+impl EcstaticSystems {
+    /// Registers a system which runs once for every message sent to it through `send_msg` or `signal_msgs_atomic`,
+    /// in the order the messages were sent. Receivers are not signaled.
+    pub fn register_receiver<'a: 'static, T: Any + Send + Sync, M: Any + Send>(&mut self, category: &str, data: &'a T, f: fn(Arc<&'a T>, M)) {
+        if self.is_shutting_down() {
+            return;
+        }
+        let (mx, rx) = mpsc::sync_channel::<M>(self.buffer_size(category));
+        let data = data_slot(data);
+        let worker_data = data.clone();
+        let reporter = self.reporter(category);
+        let state = Arc::new(SystemState::identified(reporter.id()));
+        let worker_state = state.clone();
+        let handle = self.spawn_thread(category, move || {
+            while let Ok(msg) = rx.recv() {
+                worker_state.received();
+                let start = Instant::now();
+                if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| f(worker_data.load_full(), msg))) {
+                    reporter.panicked(payload.as_ref());
+                    worker_state.exited(ExitReason::Panicked);
+                    return;
+                }
+                worker_state.complete(start.elapsed());
+            }
+            worker_state.exited(ExitReason::Normal);
+        });
+        let th = ThreadHandle {
+            thread_id: Some(handle.thread().id()),
+            join_handle: Some(handle),
+            mx: Some(Box::new(Mutex::new(mx))),
+            state,
+            data: Some(data),
+            ..Default::default()
+        };
+        self.push_handle(category, th);
+    }
+
+    /// Sends one message to a receiver, blocking while its buffer is full.
+    pub fn send_msg<M: Any + Send>(&self, category: &str, index: usize, msg: M) -> Result<(), EcstaticError> {
+        self.signal_msgs_atomic(category, index, vec![msg])
+    }
+
+    /// Sends every message to a receiver as one contiguous batch, so no message from another producer is
+    /// processed between them. Blocks while the receiver's buffer is full, keeping other producers waiting.
+    pub fn signal_msgs_atomic<M: Any + Send>(&self, category: &str, index: usize, msgs: Vec<M>) -> Result<(), EcstaticError> {
+        let th = self.handle(category, index)?;
+        let mx = th.mx.as_ref()
+            .and_then(|mx| mx.downcast_ref::<MessageSender<M>>())
+            .ok_or_else(|| EcstaticError::TypeMismatch { category: String::from(category), index })?;
+        let mx = mx.lock().unwrap();
+        for msg in msgs {
+            mx.send(msg).map_err(|_| EcstaticError::Disconnected { category: String::from(category), index })?;
+            th.state.signaled();
+        }
+        Ok(())
+    }
+
+    /// Sends every receiver in a category its own message, made by calling `transform` with `base` and the receiver's index.
+    /// Stops at the first receiver which cannot take an `M`, returning its error. Returns how many receivers were sent a message.
+    pub fn signal_fanout<B, M: Any + Send>(&self, category: &str, base: B, transform: fn(&B, usize) -> M) -> Result<usize, EcstaticError> {
+        let count = self.handles.get(category).ok_or_else(|| EcstaticError::UnknownCategory(String::from(category)))?.len();
+        for index in 0..count {
+            self.send_msg(category, index, transform(&base, index))?;
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    use crate::test_util::assert_invocations;
+    use crate::{CategoryConfig, EcstaticError, EcstaticSystems};
+
+    static RECEIVED: Mutex<Vec<(usize, usize)>> = Mutex::new(Vec::new());
+
+    fn receive(_: Arc<&AtomicUsize>, msg: (usize, usize)) {
+        RECEIVED.lock().unwrap().push(msg);
+    }
+
+    #[test]
+    fn test_signal_msgs_atomic() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.configure_category("testing", CategoryConfig { buffer_size: Some(4), ..Default::default() });
+        sys.register_receiver("testing", &ATOMIC, receive);
+        thread::scope(|s| {
+            s.spawn(|| for batch in 1..=20usize {
+                sys.signal_msgs_atomic("testing", 0, (0..10usize).map(|seq| (batch, seq)).collect()).unwrap();
+            });
+            s.spawn(|| for seq in 0..200usize {
+                sys.send_msg("testing", 0, (0usize, seq)).unwrap();
+            });
+        });
+        assert!(sys.send_msg("testing", 0, 1u8) == Err(EcstaticError::TypeMismatch { category: String::from("testing"), index: 0 }));
+        assert_invocations(&sys, "testing", 0, 400);
+        sys.drop_join_category("testing");
+
+        let received = RECEIVED.lock().unwrap().clone();
+        for batch in 1..=20usize {
+            let start = received.iter().position(|msg| msg.0 == batch).unwrap();
+            let expected: Vec<_> = (0..10).map(|seq| (batch, seq)).collect();
+            assert!(received[start..start + 10] == expected[..], "batch {} was split: {:?}", batch, &received[start..start + 10]);
+        }
+    }
+
+    #[test]
+    fn test_signal_fanout() {
+        let mut sys = EcstaticSystems::new();
+        static SLOTS: [AtomicUsize; 3] = [AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0)];
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        for slot in SLOTS.iter() {
+            sys.register_receiver("testing", slot, |x, msg: usize| x.store(msg, Ordering::SeqCst));
+        }
+        assert!(sys.signal_fanout("testing", 10usize, |base, index| base + index) == Ok(3));
+        for index in 0..3 {
+            assert_invocations(&sys, "testing", index, 1);
+        }
+        let received: Vec<usize> = SLOTS.iter().map(|slot| slot.load(Ordering::SeqCst)).collect();
+        assert!(received == vec![10, 11, 12], "Actual: {:?}", received);
+
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        let result = sys.signal_fanout("testing", 20usize, |base, index| base + index);
+        assert!(result == Err(EcstaticError::TypeMismatch { category: String::from("testing"), index: 3 }), "Actual: {:?}", result);
+        assert!(sys.signal_fanout("missing", 0usize, |base, index| base + index) == Err(EcstaticError::UnknownCategory(String::from("missing"))));
+        sys.drop_join_category("testing");
+
+        let received: Vec<usize> = SLOTS.iter().map(|slot| slot.load(Ordering::SeqCst)).collect();
+        assert!(received == vec![20, 21, 22], "Actual: {:?}", received);
+    }
+}
@@ -0,0 +1,119 @@
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{EcstaticError, EcstaticSystems};
+
+/// A single recorded signal, see `start_recording`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SignalRecord {
+    /// The category which was signaled, after aliases were resolved.
+    pub category: String,
+    /// How long after `start_recording` the signal was sent.
+    pub offset: Duration,
+}
+
+/// Every signal sent between `start_recording` and `stop_recording`, in the order they were sent.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SignalLog {
+    pub records: Vec<SignalRecord>,
+}
+
+pub(crate) struct Recording {
+    started: Instant,
+    log: SignalLog,
+}
+
+impl EcstaticSystems {
+    /// Starts recording every category signaled through `signal`, `signal_all` and `signal_class`,
+    /// discarding any recording already in progress.
+    pub fn start_recording(&self) {
+        *self.recording.lock().unwrap() = Some(Recording { started: Instant::now(), log: SignalLog::default() });
+    }
+
+    /// Stops recording and returns what was recorded, which is empty if no recording was in progress.
+    pub fn stop_recording(&self) -> SignalLog {
+        self.recording.lock().unwrap().take().map(|recording| recording.log).unwrap_or_default()
+    }
+
+    /// Signals every category in a log in the order it was recorded, without reproducing the time between signals.
+    /// Stops at the first category which is not registered.
+    pub fn replay(&self, log: &SignalLog) -> Result<(), EcstaticError> {
+        for record in log.records.iter() {
+            if !self.handles.contains_key(&record.category) {
+                return Err(EcstaticError::UnknownCategory(record.category.clone()));
+            }
+            if !self.is_frozen() {
+                self.signal_direct(&record.category);
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn record(&self, category: &str) {
+        if let Some(recording) = self.recording.lock().unwrap().as_mut() {
+            let offset = recording.started.elapsed();
+            recording.log.records.push(SignalRecord { category: String::from(category), offset });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::test_util::assert_invocations;
+    use crate::{EcstaticError, EcstaticSystems, SignalLog};
+
+    fn counts(sys: &EcstaticSystems) -> Vec<usize> {
+        ["a", "b"].iter().map(|category| sys.stats(category, 0).unwrap().count).collect()
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        static ATOMIC1: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("a", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("b", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.signal("a");
+        sys.start_recording();
+        for category in ["a", "b", "a", "a", "b"].iter() {
+            sys.signal(category);
+        }
+        let log = sys.stop_recording();
+        sys.signal("b");
+        assert!(sys.stop_recording() == SignalLog::default());
+
+        let recorded: Vec<&str> = log.records.iter().map(|record| record.category.as_str()).collect();
+        assert!(recorded == vec!["a", "b", "a", "a", "b"], "Actual: {:?}", recorded);
+        assert!(log.records.windows(2).all(|pair| pair[0].offset <= pair[1].offset));
+
+        let mut replayed = EcstaticSystems::new();
+        replayed.register_static("a", &ATOMIC1, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        replayed.register_static("b", &ATOMIC1, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        replayed.replay(&log).unwrap();
+        assert_invocations(&sys, "a", 0, 4);
+        assert_invocations(&sys, "b", 0, 3);
+        assert_invocations(&replayed, "a", 0, 3);
+        assert_invocations(&replayed, "b", 0, 2);
+        assert!(counts(&replayed) == vec![3, 2], "Actual: {:?}", counts(&replayed));
+
+        replayed.drop_join_category("b");
+        assert!(replayed.replay(&log) == Err(EcstaticError::UnknownCategory(String::from("b"))));
+        sys.clear();
+        replayed.clear();
+        assert!(ATOMIC1.load(Ordering::SeqCst) == 6, "Actual: {:?}", ATOMIC1.load(Ordering::SeqCst));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_signal_log_is_serializable() {
+        fn assert_serde<T: serde::Serialize + serde::de::DeserializeOwned>() {}
+        assert_serde::<SignalLog>();
+    }
+}
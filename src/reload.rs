@@ -0,0 +1,76 @@
+use std::any::Any;
+use std::marker::{Send, Sync};
+use std::sync::Arc;
+
+use arc_swap::{ArcSwap, Guard};
+
+use super::{data_slot, EcstaticError, EcstaticSystems};
+
+impl EcstaticSystems {
+    /// Registers a system like `register_static` which is also handed the latest value of `config` on every invocation.
+    /// The config is loaded without locking, so it can be replaced while the system runs, see `update_config`.
+    pub fn register_with_config<'a: 'static, T: Any + Send + Sync, C: Send + Sync + 'static>(&mut self, category: &str, data: &'a T, config: &'a ArcSwap<C>, f: fn(Arc<&'a T>, Guard<Arc<C>>)) {
+        if self.is_shutting_down() {
+            return;
+        }
+        let data = data_slot(data);
+        let worker_data = data.clone();
+        let mut th = self.system_create(category, move || f(worker_data.load_full(), config.load()));
+        th.data = Some(data);
+        th.config = Some(Box::new(config));
+        self.push_handle(category, th);
+    }
+
+    /// Replaces the config of a system registered with `register_with_config`. Invocations starting after this
+    /// returns see `new`, while one already in progress finishes with the config it loaded.
+    pub fn update_config<C: Send + Sync + 'static>(&self, category: &str, index: usize, new: C) -> Result<(), EcstaticError> {
+        let th = self.handle(category, index)?;
+        let config = th.config.as_ref()
+            .and_then(|config| config.downcast_ref::<&'static ArcSwap<C>>())
+            .ok_or_else(|| EcstaticError::TypeMismatch { category: String::from(category), index })?;
+        config.store(Arc::new(new));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use arc_swap::{ArcSwap, Guard};
+
+    use crate::test_util::assert_invocations;
+    use crate::{EcstaticError, EcstaticSystems};
+
+    static OBSERVED: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+    fn observe(x: Arc<&AtomicUsize>, config: Guard<Arc<u32>>) {
+        x.fetch_add(1, Ordering::SeqCst);
+        OBSERVED.lock().unwrap().push(**config);
+    }
+
+    #[test]
+    fn test_update_config() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        let config: &'static ArcSwap<u32> = Box::leak(Box::new(ArcSwap::from_pointee(1)));
+
+        sys.register_with_config("testing", &ATOMIC, config, observe);
+        sys.register_static("plain", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        for (signal, value) in [2u32, 3, 4].iter().enumerate() {
+            sys.signal("testing");
+            assert_invocations(&sys, "testing", 0, signal + 1);
+            sys.update_config("testing", 0, *value).unwrap();
+        }
+        sys.signal("testing");
+        assert_invocations(&sys, "testing", 0, 4);
+
+        assert!(sys.update_config("testing", 0, 5u64) == Err(EcstaticError::TypeMismatch { category: String::from("testing"), index: 0 }));
+        assert!(sys.update_config("plain", 0, 5u32) == Err(EcstaticError::TypeMismatch { category: String::from("plain"), index: 0 }));
+        assert!(**config.load() == 4);
+        sys.clear();
+        let observed = OBSERVED.lock().unwrap().clone();
+        assert!(observed == vec![1, 2, 3, 4], "Actual: {:?}", observed);
+    }
+}
@@ -0,0 +1,86 @@
+use super::{EcstaticError, EcstaticSystems};
+
+impl EcstaticSystems {
+    /// Moves a handle to the end of another category without touching its thread, returning its index there.
+    /// Handles after it in `from` shift down one index, and `from` stays registered even once it is empty.
+    /// The worker keeps the configuration it was spawned with, and timers created by `schedule` for `from`
+    /// keep signaling it until they are cancelled. Panics and metrics of moved and shifted handles are reported
+    /// under their new category and index.
+    pub fn move_handle(&mut self, from: &str, from_index: usize, to: &str) -> Result<usize, EcstaticError> {
+        self.handle(from, from_index)?;
+        if self.is_shutting_down() {
+            return Err(EcstaticError::ShuttingDown);
+        }
+        let ths = self.handles.get_mut(from).unwrap();
+        let th = ths.remove(from_index);
+        for (index, th) in ths.iter().enumerate().skip(from_index) {
+            th.state.label(from, index);
+        }
+        self.push_handle(to, th);
+        Ok(self.handles[to].len() - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::test_util::{assert_invocations, pump_until};
+    use crate::{CollectingSink, EcstaticError, EcstaticSystems, ErrorEvent};
+
+    #[test]
+    fn test_move_handle() {
+        let mut sys = EcstaticSystems::new();
+        static MOVED: AtomicUsize = AtomicUsize::new(0);
+        static STAYED: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("from", &MOVED, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("from", &STAYED, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("to", &STAYED, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.signal("from");
+        assert_invocations(&sys, "from", 0, 1);
+        let thread_id = sys.thread_id("from", 0);
+
+        assert!(sys.move_handle("from", 0, "to") == Ok(1));
+        assert!(sys.thread_id("to", 1) == thread_id);
+        assert!(sys.handles["to"][1].category() == "to" && sys.handles["to"][1].index() == 1);
+        assert!(sys.handles["from"][0].category() == "from" && sys.handles["from"][0].index() == 0);
+        assert!(sys.move_handle("from", 1, "to") == Err(EcstaticError::UnknownHandle { category: String::from("from"), index: 1 }));
+        sys.signal("to");
+        assert_invocations(&sys, "to", 1, 2);
+        assert!(MOVED.load(Ordering::SeqCst) == 2);
+
+        sys.signal("from");
+        assert_invocations(&sys, "from", 0, 2);
+        assert!(MOVED.load(Ordering::SeqCst) == 2);
+        assert!(sys.move_handle("from", 0, "new") == Ok(0));
+        assert!(sys.has_category("from") && sys.handles["from"].is_empty());
+        sys.clear();
+        assert!(MOVED.load(Ordering::SeqCst) == 2);
+        assert!(STAYED.load(Ordering::SeqCst) == 3, "Actual: {:?}", STAYED.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_moved_handle_reports_new_position() {
+        let sink = CollectingSink::new();
+        let mut sys = EcstaticSystems::builder().error_sink(sink.clone()).build();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("from", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("from", &ATOMIC, |_| panic!("shifted"));
+        sys.register_with_fallback("from", &ATOMIC, |_| panic!("moved"), |_| ());
+        sys.register_static("to", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        assert!(sys.move_handle("from", 2, "to") == Ok(1));
+        assert!(sys.move_handle("from", 0, "to") == Ok(2));
+        sys.signal_all();
+        pump_until(&sys, |_| sink.events().len() == 2);
+        sys.clear();
+
+        let mut events = sink.events();
+        events.sort_by_key(|event| format!("{:?}", event));
+        assert!(events == vec![
+            ErrorEvent::Panicked { category: String::from("from"), index: 0, message: String::from("shifted") },
+            ErrorEvent::Panicked { category: String::from("to"), index: 1, message: String::from("moved") },
+        ], "Actual: {:?}", events);
+    }
+}
@@ -0,0 +1,111 @@
+use std::any::Any;
+use std::marker::{Send, Sync};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::Arc;
+use std::thread;
+
+use super::{data_slot, EcstaticError, EcstaticSystems, ExitReason, SystemState, ThreadHandle};
+
+type Request<Req, Rep> = (Req, SyncSender<thread::Result<Rep>>);
+
+impl EcstaticSystems {
+    /// Registers a system which answers requests sent through `request` instead of running on signals.
+    /// If `f` panics the panic is reported to the requester and the system's thread exits.
+    pub fn register_responder<'a: 'static, T: Any + Send + Sync, Req: Any + Send, Rep: Any + Send>(&mut self, category: &str, data: &'a T, f: fn(Arc<&'a T>, Req) -> Rep) {
+        if self.is_shutting_down() {
+            return;
+        }
+        let (mx, rx) = mpsc::sync_channel::<Request<Req, Rep>>(self.buffer_size(category));
+        let data = data_slot(data);
+        let worker_data = data.clone();
+        let reporter = self.reporter(category);
+        let state = Arc::new(SystemState::identified(reporter.id()));
+        let worker_state = state.clone();
+        let handle = self.spawn_thread(category, move || {
+            while let Ok((req, reply)) = rx.recv() {
+                let rep = panic::catch_unwind(AssertUnwindSafe(|| f(worker_data.load_full(), req)));
+                let panicked = rep.is_err();
+                if let Err(payload) = rep.as_ref() {
+                    reporter.panicked(payload.as_ref());
+                }
+                let _ = reply.send(rep);
+                if panicked {
+                    worker_state.exited(ExitReason::Panicked);
+                    return;
+                }
+            }
+            worker_state.exited(ExitReason::Normal);
+        });
+        let th = ThreadHandle {
+            thread_id: Some(handle.thread().id()),
+            join_handle: Some(handle),
+            mx: Some(Box::new(mx)),
+            state,
+            data: Some(data),
+            ..Default::default()
+        };
+        self.push_handle(category, th);
+    }
+
+    /// Sends a request to a responder and blocks until it replies.
+    pub fn request<Req: Any + Send, Rep: Any + Send>(&self, category: &str, index: usize, req: Req) -> Result<Rep, EcstaticError> {
+        let th = self.handle(category, index)?;
+        let mx = th.mx.as_ref()
+            .and_then(|mx| mx.downcast_ref::<SyncSender<Request<Req, Rep>>>())
+            .ok_or_else(|| EcstaticError::TypeMismatch { category: String::from(category), index })?;
+        let disconnected = || EcstaticError::Disconnected { category: String::from(category), index };
+
+        let (reply, rx) = mpsc::sync_channel(1);
+        mx.send((req, reply)).map_err(|_| disconnected())?;
+        match rx.recv() {
+            Ok(Ok(rep)) => Ok(rep),
+            Ok(Err(_)) => Err(EcstaticError::Panicked { category: String::from(category), index }),
+            Err(_) => Err(disconnected()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::{EcstaticError, EcstaticSystems};
+
+    #[test]
+    fn test_request_reply() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_responder("testing", &ATOMIC, |x, req: usize| {
+            x.fetch_add(1, Ordering::SeqCst);
+            req + 1
+        });
+
+        assert!(sys.request::<usize, usize>("testing", 0, 41) == Ok(42));
+        assert!(sys.request::<usize, usize>("testing", 0, 1) == Ok(2));
+        assert!(sys.request::<u32, u32>("testing", 0, 1) == Err(EcstaticError::TypeMismatch { category: String::from("testing"), index: 0 }));
+        assert!(sys.request::<usize, usize>("testing", 1, 1) == Err(EcstaticError::UnknownHandle { category: String::from("testing"), index: 1 }));
+        assert!(sys.request::<usize, usize>("missing", 0, 1) == Err(EcstaticError::UnknownCategory(String::from("missing"))));
+
+        sys.drop_join_category("testing");
+        assert!(ATOMIC.load(Ordering::Relaxed) == 2);
+    }
+
+    #[test]
+    fn test_request_panicked() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_responder("testing", &ATOMIC, |_, req: usize| {
+            if req == 0 {
+                panic!("cannot respond to zero");
+            }
+            req
+        });
+
+        assert!(sys.request::<usize, usize>("testing", 0, 0) == Err(EcstaticError::Panicked { category: String::from("testing"), index: 0 }));
+        assert!(sys.request::<usize, usize>("testing", 0, 1) == Err(EcstaticError::Disconnected { category: String::from("testing"), index: 0 }));
+        sys.drop_join_category("testing");
+    }
+}
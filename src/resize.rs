@@ -0,0 +1,165 @@
+use std::collections::VecDeque;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex};
+
+use super::channel::signal_channel;
+use super::{ChannelKind, EcstaticError, EcstaticSystems};
+
+/// Receivers a worker switches to, in order, each time its current receiver disconnects.
+pub(crate) type NextReceivers = Arc<Mutex<VecDeque<Receiver<()>>>>;
+
+/// Restores the buffers enlarged by `scoped_buffer` to their prior capacity when it goes out of scope.
+/// The manager stays usable through the guard while it is alive.
+pub struct BufferGuard<'a> {
+    sys: &'a mut EcstaticSystems,
+    category: String,
+    previous: Vec<Option<usize>>,
+}
+
+impl<'a> BufferGuard<'a> {
+    pub fn category(&self) -> &str {
+        &self.category
+    }
+}
+
+impl<'a> Deref for BufferGuard<'a> {
+    type Target = EcstaticSystems;
+
+    fn deref(&self) -> &EcstaticSystems {
+        self.sys
+    }
+}
+
+impl<'a> DerefMut for BufferGuard<'a> {
+    fn deref_mut(&mut self) -> &mut EcstaticSystems {
+        self.sys
+    }
+}
+
+impl<'a> Drop for BufferGuard<'a> {
+    fn drop(&mut self) {
+        for (index, previous) in self.previous.iter().enumerate() {
+            if let Some(previous) = previous {
+                let _ = self.sys.resize_buffer(&self.category, index, *previous);
+            }
+        }
+    }
+}
+
+impl EcstaticSystems {
+    /// How many signals a handle buffers before `signal` blocks, or `usize::MAX` if its channel is unbounded.
+    pub fn buffer_capacity(&self, category: &str, index: usize) -> Result<usize, EcstaticError> {
+        Ok(self.handle(category, index)?.capacity)
+    }
+
+    /// Changes how many signals a handle buffers without blocking, even while its buffer is full.
+    /// Signals already buffered still run, before any sent after the resize. An unbounded handle becomes bounded.
+    /// Returns `EcstaticError::Unsupported` while a timer created by `schedule` signals the handle, since the worker
+    /// could not move on to the new buffer until the timer is cancelled.
+    pub fn resize_buffer(&mut self, category: &str, index: usize, new_capacity: usize) -> Result<(), EcstaticError> {
+        if self.is_scheduled(&self.handle(category, index)?.state) {
+            return Err(EcstaticError::Unsupported { category: String::from(category), index });
+        }
+        let th = self.handles.get_mut(category).unwrap().get_mut(index).unwrap();
+        let next_rx = match (th.next_rx.as_ref(), th.sx.as_ref()) {
+            (Some(next_rx), Some(_)) => next_rx,
+            _ => return Err(EcstaticError::Unsupported { category: String::from(category), index }),
+        };
+        let (sx, rx, _) = signal_channel(ChannelKind::Bounded(new_capacity));
+        //the worker has to find the new receiver once the old sender is dropped and its buffer drained.
+        next_rx.lock().unwrap().push_back(rx);
+        mem::drop(th.sx.replace(sx));
+        th.capacity = new_capacity;
+        Ok(())
+    }
+
+    /// Resizes every handle in a category like `resize_buffer` until the returned guard is dropped, at which point
+    /// each one is resized back to the capacity it had before. Handles which cannot be resized and handles with
+    /// an unbounded channel are left as they are.
+    pub fn scoped_buffer(&mut self, category: &str, capacity: usize) -> Result<BufferGuard<'_>, EcstaticError> {
+        let previous: Vec<Option<usize>> = self.handles.get(category)
+            .ok_or_else(|| EcstaticError::UnknownCategory(String::from(category)))?
+            .iter()
+            .map(|th| Some(th.capacity).filter(|capacity| *capacity != usize::MAX))
+            .collect();
+        let mut resized = Vec::with_capacity(previous.len());
+        for (index, previous) in previous.into_iter().enumerate() {
+            let resizable = previous.is_some() && self.resize_buffer(category, index, capacity).is_ok();
+            resized.push(previous.filter(|_| resizable));
+        }
+        Ok(BufferGuard { sys: self, category: String::from(category), previous: resized })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use crate::test_util::{assert_invocations, gated, pump_until, Gated};
+    use crate::{CategoryConfig, EcstaticError, EcstaticSystems};
+
+    #[test]
+    fn test_resize_buffer() {
+        let mut sys = EcstaticSystems::new();
+        static GATED: Gated = Gated::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.configure_category("testing", CategoryConfig { buffer_size: Some(1), ..Default::default() });
+        sys.register_static("testing", &GATED, gated);
+        sys.register_inline("inline", &ATOMIC, |_| ());
+        sys.signal("testing");
+        pump_until(&sys, |_| GATED.runs.load(Ordering::SeqCst) == 1);
+        sys.signal("testing");
+        let full = EcstaticError::Full { category: String::from("testing"), index: 0 };
+        assert!(sys.signal_retry("testing", 1, Duration::ZERO) == Err(full));
+
+        sys.resize_buffer("testing", 0, 10).unwrap();
+        assert!(sys.buffer_capacity("testing", 0) == Ok(10));
+        for _ in 0..10 {
+            sys.signal_retry("testing", 1, Duration::ZERO).unwrap();
+        }
+        assert!(sys.resize_buffer("inline", 0, 10) == Err(EcstaticError::Unsupported { category: String::from("inline"), index: 0 }));
+        let timer = sys.schedule("testing", Duration::from_secs(60)).unwrap();
+        assert!(sys.resize_buffer("testing", 0, 2) == Err(EcstaticError::Unsupported { category: String::from("testing"), index: 0 }));
+        assert!(sys.buffer_capacity("testing", 0) == Ok(10));
+        assert!(sys.cancel_schedule(timer.id()));
+        sys.resize_buffer("testing", 0, 2).unwrap();
+        sys.signal("testing");
+
+        GATED.released.store(true, Ordering::SeqCst);
+        assert_invocations(&sys, "testing", 0, 13);
+        sys.clear();
+        assert!(GATED.runs.load(Ordering::SeqCst) == 13);
+    }
+
+    #[test]
+    fn test_scoped_buffer() {
+        let mut sys = EcstaticSystems::new();
+        static GATED: Gated = Gated::new();
+
+        sys.configure_category("testing", CategoryConfig { buffer_size: Some(1), ..Default::default() });
+        sys.register_static("testing", &GATED, gated);
+        sys.signal("testing");
+        pump_until(&sys, |_| GATED.runs.load(Ordering::SeqCst) == 1);
+        {
+            let guard = sys.scoped_buffer("testing", 5).unwrap();
+            assert!(guard.buffer_capacity("testing", 0) == Ok(5));
+            for _ in 0..5 {
+                guard.signal_retry("testing", 1, Duration::ZERO).unwrap();
+            }
+        }
+        assert!(sys.buffer_capacity("testing", 0) == Ok(1));
+        sys.signal_retry("testing", 1, Duration::ZERO).unwrap();
+        let full = EcstaticError::Full { category: String::from("testing"), index: 0 };
+        assert!(sys.signal_retry("testing", 1, Duration::ZERO) == Err(full));
+        assert!(sys.scoped_buffer("missing", 5).err() == Some(EcstaticError::UnknownCategory(String::from("missing"))));
+
+        GATED.released.store(true, Ordering::SeqCst);
+        assert_invocations(&sys, "testing", 0, 7);
+        sys.clear();
+        assert!(GATED.runs.load(Ordering::SeqCst) == 7);
+    }
+}
@@ -0,0 +1,76 @@
+use std::sync::mpsc::TrySendError;
+use std::thread;
+use std::time::Duration;
+
+use super::{EcstaticError, EcstaticSystems};
+
+impl EcstaticSystems {
+    /// Signals every thread handle in a category without blocking indefinitely on a full buffer.
+    /// Each handle is tried up to `attempts` times, sleeping for `backoff` between attempts. Every handle is tried
+    /// at least once, so 0 attempts behaves like 1.
+    /// Stops at the first handle which could not be signaled; handles before it keep their signal.
+    pub fn signal_retry(&self, category: &str, attempts: usize, backoff: Duration) -> Result<(), EcstaticError> {
+        let ths = self.handles.get(category).ok_or_else(|| EcstaticError::UnknownCategory(String::from(category)))?;
+        if self.is_frozen() {
+            return Ok(());
+        }
+        for (index, th) in ths.iter().enumerate() {
+            let sx = match th.sx.as_ref() {
+                Some(sx) => sx,
+                None => continue,
+            };
+            let mut attempt = 0;
+            loop {
+                match sx.try_send(()) {
+                    Ok(()) => {
+                        th.state.signaled();
+                        break;
+                    }
+                    Err(TrySendError::Full(())) if attempt + 1 < attempts => {
+                        attempt += 1;
+                        thread::sleep(backoff);
+                    }
+                    Err(TrySendError::Full(())) => return Err(EcstaticError::Full { category: String::from(category), index }),
+                    Err(TrySendError::Disconnected(())) => return Err(EcstaticError::Disconnected { category: String::from(category), index }),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::test_util::{gated, pump_until, Gated};
+    use crate::{CategoryConfig, EcstaticError, EcstaticSystems};
+
+    #[test]
+    fn test_signal_retry() {
+        let mut sys = EcstaticSystems::new();
+        static GATED: Gated = Gated::new();
+
+        sys.configure_category("testing", CategoryConfig { buffer_size: Some(1), ..Default::default() });
+        sys.register_static("testing", &GATED, gated);
+        sys.signal("testing");
+        pump_until(&sys, |_| GATED.runs.load(Ordering::SeqCst) > 0);
+        sys.signal("testing");
+
+        assert!(sys.signal_retry("testing", 1, Duration::from_millis(0)) == Err(EcstaticError::Full { category: String::from("testing"), index: 0 }));
+        assert!(sys.signal_retry("testing", 0, Duration::from_millis(0)) == Err(EcstaticError::Full { category: String::from("testing"), index: 0 }));
+        assert!(sys.signal_retry("missing", 1, Duration::from_millis(0)) == Err(EcstaticError::UnknownCategory(String::from("missing"))));
+
+        let releaser = thread::spawn(|| {
+            thread::sleep(Duration::from_millis(50));
+            GATED.released.store(true, Ordering::SeqCst);
+        });
+        assert!(sys.signal_retry("testing", 1000, Duration::from_millis(5)) == Ok(()));
+        releaser.join().unwrap();
+        sys.drop_join_category("testing");
+
+        assert!(GATED.runs.load(Ordering::Relaxed) == 3);
+    }
+}
@@ -0,0 +1,295 @@
+use std::any::Any;
+use std::collections::VecDeque;
+use std::marker::{Send, Sync};
+use std::mem;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+use super::{data_slot, CancelToken, EcstaticSystems};
+
+/// How long a worker blocked on a full `ResultBuffer` waits before checking whether its category is being torn down.
+const CANCEL_POLL: Duration = Duration::from_millis(10);
+
+/// What a returning system does with a new result while its buffer is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// The oldest buffered result is discarded to make room.
+    DropOldest,
+    /// The new result is discarded.
+    DropNewest,
+    /// The worker waits until results are collected. Once its category is being joined it stops waiting
+    /// and buffers the result anyway, so joining never blocks on a full buffer.
+    Block,
+}
+
+/// Bounds how many results a returning system buffers, see `register_returning_bounded`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResultBuffer {
+    pub capacity: usize,
+    pub policy: OverflowPolicy,
+}
+
+impl ResultBuffer {
+    pub fn new(capacity: usize, policy: OverflowPolicy) -> ResultBuffer {
+        ResultBuffer { capacity, policy }
+    }
+}
+
+/// The results a returning system has produced and which have not been collected yet.
+struct Results<R> {
+    values: Mutex<VecDeque<R>>,
+    collected: Condvar,
+    bound: Option<ResultBuffer>,
+}
+
+impl<R> Results<R> {
+    fn new(bound: Option<ResultBuffer>) -> Results<R> {
+        Results { values: Mutex::new(VecDeque::new()), collected: Condvar::new(), bound }
+    }
+
+    fn push(&self, r: R, token: &CancelToken) {
+        let mut values = self.values.lock().unwrap();
+        match self.bound {
+            Some(ResultBuffer { capacity, policy: OverflowPolicy::DropOldest }) if values.len() >= capacity => {
+                values.pop_front();
+                if capacity == 0 {
+                    return;
+                }
+            }
+            Some(ResultBuffer { capacity, policy: OverflowPolicy::DropNewest }) if values.len() >= capacity => return,
+            Some(ResultBuffer { capacity, policy: OverflowPolicy::Block }) => {
+                while values.len() >= capacity && !token.is_cancelled() {
+                    values = self.collected.wait_timeout(values, CANCEL_POLL).unwrap().0;
+                }
+            }
+            _ => (),
+        }
+        values.push_back(r);
+    }
+
+    fn take(&self) -> Vec<R> {
+        let values = mem::take(&mut *self.values.lock().unwrap());
+        self.collected.notify_all();
+        values.into()
+    }
+}
+
+impl EcstaticSystems {
+    /// Registers a system like `register_static` whose function returns a value every time it is signaled.
+    /// Returned values are buffered on the handle until they are collected.
+    pub fn register_returning<'a: 'static, T: Any + Send + Sync, R: Any + Send>(&mut self, category: &str, data: &'a T, f: fn(Arc<&'a T>) -> R) {
+        self.returning_create(category, data, f, None);
+    }
+
+    /// Registers a returning system which buffers at most `buffer.capacity` results, applying `buffer.policy`
+    /// to any result produced while the buffer is full.
+    pub fn register_returning_bounded<'a: 'static, T: Any + Send + Sync, R: Any + Send>(&mut self, category: &str, data: &'a T, f: fn(Arc<&'a T>) -> R, buffer: ResultBuffer) {
+        self.returning_create(category, data, f, Some(buffer));
+    }
+
+    fn returning_create<'a: 'static, T: Any + Send + Sync, R: Any + Send>(&mut self, category: &str, data: &'a T, f: fn(Arc<&'a T>) -> R, bound: Option<ResultBuffer>) {
+        if self.is_shutting_down() {
+            return;
+        }
+        let results: Arc<Results<R>> = Arc::new(Results::new(bound));
+        let worker_results = results.clone();
+        let token = CancelToken::new();
+        let worker_token = token.clone();
+        let data = data_slot(data);
+        let worker_data = data.clone();
+        let mut th = self.system_create(category, move || {
+            let r = f(worker_data.load_full());
+            worker_results.push(r, &worker_token);
+        });
+        th.results = Some(results);
+        th.cancel = Some(token);
+        th.data = Some(data);
+        self.push_handle(category, th);
+    }
+
+    /// Registers a system like `register_stateful` whose `run` returns a value every time it is signaled, buffered
+    /// like `register_returning`. The state is kept once the worker exits, see `drain_join_full`.
+    pub fn register_stateful_returning<S: Any + Send, R: Any + Send>(&mut self, category: &str, init: fn() -> S, run: fn(&mut S) -> R) {
+        if self.is_shutting_down() {
+            return;
+        }
+        let results: Arc<Results<R>> = Arc::new(Results::new(None));
+        let worker_results = results.clone();
+        let token = CancelToken::new();
+        let worker_token = token.clone();
+        let final_state: Arc<Mutex<Option<S>>> = Arc::new(Mutex::new(None));
+        let worker_final_state = final_state.clone();
+        let mut th = self.system_create_with(category, init, move |s| {
+            worker_results.push(run(s), &worker_token);
+            true
+        }, move |s| *worker_final_state.lock().unwrap() = Some(s));
+        th.results = Some(results);
+        th.cancel = Some(token);
+        th.final_state = Some(final_state);
+        self.push_handle(category, th);
+    }
+
+    /// Drains the buffered results of every handle in a category, one list per handle in registration order.
+    /// Handles which are not returning systems of type `R` yield an empty list.
+    pub fn collect_results<R: Any + Send>(&self, category: &str) -> Vec<Vec<R>> {
+        match self.handles.get(category) {
+            Some(ths) => ths.iter().map(|th| drain::<R>(&th.results)).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Joins a category like `drop_join_category` and returns every result its returning systems produced
+    /// which had not been collected yet, including those from signals still buffered when it was dropped.
+    pub fn drop_join_category_collect<R: Any + Send>(&mut self, category: &str) -> Vec<Vec<R>> {
+        let results: Vec<_> = match self.handles.get(category) {
+            Some(ths) => ths.iter().map(|th| th.results.clone()).collect(),
+            None => Vec::new(),
+        };
+        self.drop_join_category(category);
+        results.iter().map(drain::<R>).collect()
+    }
+
+    /// Joins a category like `drop_join_category_collect`, returning each handle's uncollected results together with
+    /// the state it ended with, in registration order. The state is `None` for handles which are not stateful returning
+    /// systems with state `S`, and for workers which exited by panicking.
+    pub fn drain_join_full<R: Any + Send, S: Any + Send>(&mut self, category: &str) -> Vec<(Vec<R>, Option<S>)> {
+        let kept: Vec<_> = match self.handles.get(category) {
+            Some(ths) => ths.iter().map(|th| (th.results.clone(), th.final_state.clone())).collect(),
+            None => Vec::new(),
+        };
+        self.drop_join_category(category);
+        kept.iter()
+            .map(|(results, final_state)| (drain::<R>(results), take_final_state::<S>(final_state)))
+            .collect()
+    }
+}
+
+fn take_final_state<S: Any + Send>(final_state: &Option<Arc<dyn Any + Send + Sync>>) -> Option<S> {
+    final_state.as_ref()?
+        .downcast_ref::<Mutex<Option<S>>>()?
+        .lock().unwrap()
+        .take()
+}
+
+pub(crate) fn drain<R: Any + Send>(results: &Option<Arc<dyn Any + Send + Sync>>) -> Vec<R> {
+    results.as_ref()
+        .and_then(|results| results.downcast_ref::<Results<R>>())
+        .map(Results::take)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    use super::{OverflowPolicy, ResultBuffer};
+    use crate::test_util::{assert_invocations, pump_until};
+    use crate::EcstaticSystems;
+
+    #[test]
+    fn test_collect_results() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        static ATOMIC1: AtomicUsize = AtomicUsize::new(10);
+        static ATOMIC2: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_returning("testing", &ATOMIC, |x| x.fetch_add(1, Ordering::SeqCst));
+        sys.register_returning("testing", &ATOMIC1, |x| x.fetch_add(1, Ordering::SeqCst));
+        sys.register_static("testing", &ATOMIC2, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        for _ in 0..3 {
+            sys.signal("testing");
+        }
+
+        let mut collected: Vec<Vec<usize>> = vec![Vec::new(); 3];
+        while collected.iter().map(Vec::len).sum::<usize>() < 6 {
+            let results = sys.collect_results::<usize>("testing");
+            assert!(results.len() == 3);
+            for (all, new) in collected.iter_mut().zip(results) {
+                all.extend(new);
+            }
+            thread::yield_now();
+        }
+        sys.drop_join_category("testing");
+
+        assert!(collected == vec![vec![0, 1, 2], vec![10, 11, 12], vec![]], "Actual: {:?}", collected);
+        assert!(sys.collect_results::<usize>("testing").is_empty());
+    }
+
+    #[test]
+    fn test_drop_join_category_collect() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        static ATOMIC1: AtomicUsize = AtomicUsize::new(10);
+
+        sys.register_returning("testing", &ATOMIC, |x| x.fetch_add(1, Ordering::SeqCst));
+        sys.register_returning("testing", &ATOMIC1, |x| x.fetch_add(1, Ordering::SeqCst));
+        for _ in 0..3 {
+            sys.signal("testing");
+        }
+        let results = sys.drop_join_category_collect::<usize>("testing");
+
+        assert!(results == vec![vec![0, 1, 2], vec![10, 11, 12]], "Actual: {:?}", results);
+        assert!(!sys.handles.contains_key("testing"));
+    }
+
+    struct Tally {
+        seen: Vec<usize>,
+        total: usize,
+    }
+
+    #[test]
+    fn test_drain_join_full() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_stateful_returning("testing", || Tally { seen: Vec::new(), total: 0 }, |s| {
+            s.total += s.seen.len();
+            s.seen.push(s.total);
+            s.total
+        });
+        sys.register_stateful_returning("testing", || Tally { seen: vec![100], total: 100 }, |s| {
+            s.total += 1;
+            s.total
+        });
+        sys.register_returning("testing", &ATOMIC, |x| x.fetch_add(1, Ordering::SeqCst));
+        for _ in 0..4 {
+            sys.signal("testing");
+        }
+        let drained = sys.drain_join_full::<usize, Tally>("testing");
+
+        let results: Vec<&Vec<usize>> = drained.iter().map(|(results, _)| results).collect();
+        assert!(results == vec![&vec![0, 1, 3, 6], &vec![101, 102, 103, 104], &vec![0, 1, 2, 3]], "Actual: {:?}", results);
+        let states: Vec<Option<(&Vec<usize>, usize)>> = drained.iter().map(|(_, s)| s.as_ref().map(|s| (&s.seen, s.total))).collect();
+        assert!(states == vec![Some((&vec![0, 1, 3, 6], 6)), Some((&vec![100], 104)), None], "Actual: {:?}", states);
+        assert!(sys.drain_join_full::<usize, Tally>("testing").is_empty());
+    }
+
+    #[test]
+    fn test_result_buffer_overflow() {
+        let mut sys = EcstaticSystems::new();
+        static OLDEST: AtomicUsize = AtomicUsize::new(0);
+        static NEWEST: AtomicUsize = AtomicUsize::new(0);
+        static BLOCK: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_returning_bounded("oldest", &OLDEST, |x| x.fetch_add(1, Ordering::SeqCst), ResultBuffer::new(3, OverflowPolicy::DropOldest));
+        sys.register_returning_bounded("newest", &NEWEST, |x| x.fetch_add(1, Ordering::SeqCst), ResultBuffer::new(3, OverflowPolicy::DropNewest));
+        sys.register_returning_bounded("block", &BLOCK, |x| x.fetch_add(1, Ordering::SeqCst), ResultBuffer::new(3, OverflowPolicy::Block));
+        for _ in 0..10 {
+            sys.signal_all();
+        }
+        assert_invocations(&sys, "oldest", 0, 10);
+        assert_invocations(&sys, "newest", 0, 10);
+        assert!(sys.collect_results::<usize>("oldest") == vec![vec![7, 8, 9]]);
+        assert!(sys.collect_results::<usize>("newest") == vec![vec![0, 1, 2]]);
+
+        pump_until(&sys, |_| BLOCK.load(Ordering::SeqCst) == 4);
+        assert_invocations(&sys, "block", 0, 3);
+        assert!(sys.collect_results::<usize>("block") == vec![vec![0, 1, 2]]);
+        pump_until(&sys, |_| BLOCK.load(Ordering::SeqCst) == 7);
+        assert_invocations(&sys, "block", 0, 6);
+        let results = sys.drop_join_category_collect::<usize>("block");
+        assert!(results == vec![(3..10).collect::<Vec<usize>>()], "Actual: {:?}", results);
+        sys.clear();
+    }
+}
@@ -0,0 +1,57 @@
+use super::{EcstaticError, EcstaticSystems};
+
+impl EcstaticSystems {
+    /// Makes a handle's worker run its system on only every `one_in`th signal it receives, dropping the rest without
+    /// running anything. Dropped signals still count as completed, so waiting on the handle does not hang, but they
+    /// are not counted as invocations in the handle's `stats`.
+    /// A rate of 0 or 1 runs every signal again. Only handles with a signaled worker of their own can be sampled.
+    pub fn set_sample_rate(&self, category: &str, index: usize, one_in: u32) -> Result<(), EcstaticError> {
+        let th = self.handle(category, index)?;
+        if th.next_rx.is_none() {
+            return Err(EcstaticError::Unsupported { category: String::from(category), index });
+        }
+        th.state.set_sample_rate(one_in);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use crate::test_util::{assert_invocations, DEFAULT_TIMEOUT};
+    use crate::{EcstaticError, EcstaticSystems};
+
+    #[test]
+    fn test_set_sample_rate() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        static INLINE: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_inline("inline", &INLINE, |_| ());
+        sys.set_sample_rate("testing", 0, 100).unwrap();
+        for _ in 0..50 {
+            sys.signal("testing");
+        }
+        sys.wait_category_idle("testing", DEFAULT_TIMEOUT).unwrap();
+        let stats = sys.stats("testing", 0).unwrap();
+        assert!(stats.count == 0 && stats.min_run_time == Duration::ZERO, "Actual: {:?}", stats);
+
+        for _ in 0..950 {
+            sys.signal("testing");
+        }
+        assert_invocations(&sys, "testing", 0, 10);
+        assert!(ATOMIC.load(Ordering::SeqCst) == 10, "Actual: {:?}", ATOMIC.load(Ordering::SeqCst));
+
+        sys.set_sample_rate("testing", 0, 0).unwrap();
+        for _ in 0..5 {
+            sys.signal("testing");
+        }
+        assert_invocations(&sys, "testing", 0, 15);
+        assert!(ATOMIC.load(Ordering::SeqCst) == 15, "Actual: {:?}", ATOMIC.load(Ordering::SeqCst));
+        assert!(sys.set_sample_rate("inline", 0, 2) == Err(EcstaticError::Unsupported { category: String::from("inline"), index: 0 }));
+        sys.clear();
+    }
+}
@@ -0,0 +1,234 @@
+use std::mem;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use super::{EcstaticError, EcstaticSystems, SystemState};
+
+/// Identifies a timer created by `schedule`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ScheduleHandle {
+    id: u64,
+}
+
+impl ScheduleHandle {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+/// A description of a running timer, see `list_schedules`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScheduleInfo {
+    pub id: u64,
+    pub category: String,
+    pub interval: Duration,
+}
+
+/// Randomizes the delay before each tick of a timer, see `schedule_jittered`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Jitter {
+    /// How far each delay may stray from the interval, as a fraction of it between 0 and 1.
+    /// Values outside that range are clamped into it, and NaN or infinite values disable jitter.
+    pub fraction: f64,
+    /// Seeds the generator, so the same seed always produces the same delays.
+    pub seed: u64,
+}
+
+impl Jitter {
+    pub fn new(fraction: f64, seed: u64) -> Jitter {
+        Jitter { fraction, seed }
+    }
+
+    /// The delays before each tick, uniformly spread over `interval` plus or minus `fraction` of it.
+    fn delays(self, interval: Duration) -> impl Iterator<Item = Duration> {
+        let fraction = if self.fraction.is_finite() { self.fraction.clamp(0.0, 1.0) } else { 0.0 };
+        let mut state = self.seed;
+        std::iter::repeat_with(move || {
+            //splitmix64, the top 53 bits give a uniform float in [0, 1).
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^= z >> 31;
+            let unit = (z >> 11) as f64 / (1u64 << 53) as f64;
+            interval.mul_f64(1.0 + fraction * (2.0 * unit - 1.0))
+        })
+    }
+}
+
+/// A running timer. Dropping `stop` wakes the timer thread and makes it exit.
+pub(crate) struct Schedule {
+    category: String,
+    interval: Duration,
+    states: Vec<Arc<SystemState>>,
+    stop: Option<Sender<()>>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl Schedule {
+    fn cancel(mut self) {
+        mem::drop(self.stop.take());
+        if let Some(join_handle) = self.join_handle.take() {
+            let _ = join_handle.join();
+        }
+    }
+}
+
+impl EcstaticSystems {
+    /// Signals every handle a category has when this is called, once every `interval`, until the timer is
+    /// cancelled or the category is dropped. Ticks skip handles whose buffer is full instead of blocking.
+    pub fn schedule(&mut self, category: &str, interval: Duration) -> Result<ScheduleHandle, EcstaticError> {
+        self.schedule_create(category, interval, None)
+    }
+
+    /// Schedules a category like `schedule`, but waits a random delay within `jitter` of the interval before
+    /// every tick, so timers sharing an interval do not all wake at once. Ticks still average `interval`.
+    pub fn schedule_jittered(&mut self, category: &str, interval: Duration, jitter: Jitter) -> Result<ScheduleHandle, EcstaticError> {
+        self.schedule_create(category, interval, Some(jitter))
+    }
+
+    fn schedule_create(&mut self, category: &str, interval: Duration, jitter: Option<Jitter>) -> Result<ScheduleHandle, EcstaticError> {
+        let ths = self.handles.get(category).ok_or_else(|| EcstaticError::UnknownCategory(String::from(category)))?;
+        let targets: Vec<_> = ths.iter()
+            .filter_map(|th| Some((th.sx.clone()?, th.state.clone())))
+            .collect();
+        let states = targets.iter().map(|(_, state)| state.clone()).collect();
+        let (stop, stopped) = mpsc::channel::<()>();
+        let mut delays: Box<dyn Iterator<Item = Duration> + Send> = match jitter {
+            Some(jitter) => Box::new(jitter.delays(interval)),
+            None => Box::new(std::iter::repeat(interval)),
+        };
+        let join_handle = self.spawn_helper(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(delays.next().unwrap()) {
+                for (sx, state) in targets.iter() {
+                    if sx.try_send(()).is_ok() {
+                        state.signaled();
+                    }
+                }
+            }
+        });
+
+        let id = self.next_schedule;
+        self.next_schedule += 1;
+        self.schedules.insert(id, Schedule {
+            category: String::from(category),
+            interval,
+            states,
+            stop: Some(stop),
+            join_handle: Some(join_handle),
+        });
+        Ok(ScheduleHandle { id })
+    }
+
+    /// Every timer which is still running, ordered by id.
+    pub fn list_schedules(&self) -> Vec<ScheduleInfo> {
+        let mut infos: Vec<_> = self.schedules.iter()
+            .map(|(id, schedule)| ScheduleInfo { id: *id, category: schedule.category.clone(), interval: schedule.interval })
+            .collect();
+        infos.sort_unstable_by_key(|info| info.id);
+        infos
+    }
+
+    /// Stops a timer and waits for its thread to exit. Returns false if no timer has this id.
+    pub fn cancel_schedule(&mut self, id: u64) -> bool {
+        match self.schedules.remove(&id) {
+            Some(schedule) => {
+                schedule.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether a timer holds a sender to the worker with this state, which keeps its receiver connected.
+    pub(crate) fn is_scheduled(&self, state: &Arc<SystemState>) -> bool {
+        self.schedules.values().any(|schedule| schedule.states.iter().any(|scheduled| Arc::ptr_eq(scheduled, state)))
+    }
+
+    /// Timers hold senders to their category's workers, so they are stopped before the category is joined.
+    pub(crate) fn cancel_category_schedules(&mut self, category: &str) {
+        let ids: Vec<u64> = self.schedules.iter()
+            .filter(|(_, schedule)| schedule.category == category)
+            .map(|(id, _)| *id)
+            .collect();
+        for id in ids {
+            self.cancel_schedule(id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    use super::{Jitter, ScheduleInfo};
+    use crate::test_util::pump_until;
+    use crate::{EcstaticError, EcstaticSystems};
+
+    #[test]
+    fn test_list_and_cancel_schedules() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        static ATOMIC1: AtomicUsize = AtomicUsize::new(0);
+        let interval = Duration::from_millis(2);
+
+        sys.register_static("first", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("second", &ATOMIC1, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        let first = sys.schedule("first", interval).unwrap();
+        let second = sys.schedule("second", interval).unwrap();
+        assert!(sys.schedule("missing", interval) == Err(EcstaticError::UnknownCategory(String::from("missing"))));
+
+        let infos = sys.list_schedules();
+        assert!(infos == vec![
+            ScheduleInfo { id: first.id(), category: String::from("first"), interval },
+            ScheduleInfo { id: second.id(), category: String::from("second"), interval },
+        ], "Actual: {:?}", infos);
+        pump_until(&sys, |_| ATOMIC.load(Ordering::SeqCst) > 0 && ATOMIC1.load(Ordering::SeqCst) > 0);
+
+        assert!(sys.cancel_schedule(first.id()));
+        assert!(!sys.cancel_schedule(first.id()));
+        assert!(sys.list_schedules().iter().map(|info| info.id).eq([second.id()].iter().copied()));
+        pump_until(&sys, |sys| sys.handle("first", 0).unwrap().state.idle());
+        let cancelled = ATOMIC.load(Ordering::SeqCst);
+        let running = ATOMIC1.load(Ordering::SeqCst);
+        thread::sleep(interval * 20);
+
+        assert!(ATOMIC.load(Ordering::SeqCst) == cancelled, "Actual: {:?} ; Expected: {:?}", ATOMIC.load(Ordering::SeqCst), cancelled);
+        assert!(ATOMIC1.load(Ordering::SeqCst) > running);
+        sys.clear();
+        assert!(sys.list_schedules().is_empty());
+    }
+
+    #[test]
+    fn test_jitter_delays() {
+        let interval = Duration::from_millis(100);
+        let delays: Vec<Duration> = Jitter::new(0.2, 7).delays(interval).take(1000).collect();
+
+        assert!(delays.iter().all(|delay| *delay >= interval.mul_f64(0.8) && *delay <= interval.mul_f64(1.2)), "Actual: {:?}", delays);
+        assert!(delays.iter().any(|delay| *delay < interval.mul_f64(0.85)) && delays.iter().any(|delay| *delay > interval.mul_f64(1.15)));
+        let avg = delays.iter().sum::<Duration>() / delays.len() as u32;
+        assert!(avg >= interval.mul_f64(0.98) && avg <= interval.mul_f64(1.02), "Actual: {:?}", avg);
+        assert!(Jitter::new(0.2, 7).delays(interval).take(1000).eq(delays.iter().copied()));
+        assert!(!Jitter::new(0.2, 8).delays(interval).take(1000).eq(delays.iter().copied()));
+        assert!(Jitter::new(0.0, 7).delays(interval).take(10).all(|delay| delay == interval));
+        assert!(Jitter::new(f64::NAN, 7).delays(interval).take(10).all(|delay| delay == interval));
+        assert!(Jitter::new(f64::INFINITY, 7).delays(interval).take(10).all(|delay| delay == interval));
+    }
+
+    #[test]
+    fn test_schedule_jittered() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        let interval = Duration::from_millis(2);
+
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        let handle = sys.schedule_jittered("testing", interval, Jitter::new(0.5, 42)).unwrap();
+        assert!(sys.list_schedules() == vec![ScheduleInfo { id: handle.id(), category: String::from("testing"), interval }]);
+        pump_until(&sys, |_| ATOMIC.load(Ordering::SeqCst) >= 5);
+        sys.clear();
+    }
+}
@@ -0,0 +1,89 @@
+use std::any::Any;
+use std::marker::{Send, Sync};
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use super::{data_slot, EcstaticSystems};
+
+/// Per-worker storage a system keeps across invocations, see `register_with_scratch`.
+/// Created on the worker thread and only ever touched from it.
+#[derive(Debug, Default)]
+pub struct Scratch<S> {
+    value: S,
+    uses: usize,
+}
+
+impl<S> Scratch<S> {
+    /// How many earlier invocations have used this scratch.
+    pub fn uses(&self) -> usize {
+        self.uses
+    }
+}
+
+impl<S> Deref for Scratch<S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        &self.value
+    }
+}
+
+impl<S> DerefMut for Scratch<S> {
+    fn deref_mut(&mut self) -> &mut S {
+        &mut self.value
+    }
+}
+
+impl EcstaticSystems {
+    /// Registers a system like `register_static` which is also passed a scratch value of its own, such as a buffer,
+    /// that persists across its invocations so it does not have to be reallocated for every signal.
+    /// The scratch starts out as `S::default()` on the worker thread.
+    pub fn register_with_scratch<'a: 'static, T: Any + Send + Sync, S: Default + 'static>(&mut self, category: &str, data: &'a T, f: fn(Arc<&'a T>, &mut Scratch<S>)) {
+        if self.is_shutting_down() {
+            return;
+        }
+        let data = data_slot(data);
+        let worker_data = data.clone();
+        let mut th = self.system_create_with(category, Scratch::<S>::default, move |scratch| {
+            f(worker_data.load_full(), scratch);
+            scratch.uses += 1;
+            true
+        }, |_| ());
+        th.data = Some(data);
+        self.push_handle(category, th);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::Scratch;
+    use crate::test_util::assert_invocations;
+    use crate::EcstaticSystems;
+
+    fn fill(x: Arc<&Mutex<Vec<(usize, usize)>>>, buffer: &mut Scratch<Vec<u8>>) {
+        let len = 64 >> buffer.uses().min(3);
+        buffer.clear();
+        buffer.resize(len, 0);
+        x.lock().unwrap().push((buffer.uses(), buffer.capacity()));
+    }
+
+    #[test]
+    fn test_register_with_scratch() {
+        let mut sys = EcstaticSystems::new();
+        static SEEN: Mutex<Vec<(usize, usize)>> = Mutex::new(Vec::new());
+
+        sys.register_with_scratch("testing", &SEEN, fill);
+        for _ in 0..4 {
+            sys.signal("testing");
+        }
+        assert_invocations(&sys, "testing", 0, 4);
+        sys.clear();
+
+        //later invocations need less room, so a reused buffer keeps the capacity of the first.
+        let seen = SEEN.lock().unwrap().clone();
+        assert!(seen.iter().map(|(uses, _)| *uses).eq(0..4), "Actual: {:?}", seen);
+        assert!(seen.iter().all(|(_, capacity)| *capacity == seen[0].1 && *capacity >= 64), "Actual: {:?}", seen);
+    }
+}
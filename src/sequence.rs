@@ -0,0 +1,116 @@
+use std::any::Any;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::marker::{Send, Sync};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::Arc;
+use std::time::Instant;
+
+use super::{data_slot, EcstaticError, EcstaticSystems, ExitReason, SystemState, ThreadHandle};
+
+/// The send path of a sequenced system. Sequences are stamped before sending, so they may arrive out of order.
+struct Sequencer {
+    next: AtomicU64,
+    sx: SyncSender<u64>,
+}
+
+impl EcstaticSystems {
+    /// Registers a system which runs once for every signal sent to it through `signal_seq`, with the sequence number
+    /// that signal was stamped with. Sequences start at 0 and are processed strictly in order, even when producers race
+    /// and deliver them out of order. Sequenced systems are not signaled by `signal`.
+    pub fn register_sequenced<'a: 'static, T: Any + Send + Sync>(&mut self, category: &str, data: &'a T, f: fn(Arc<&'a T>, u64)) {
+        if self.is_shutting_down() {
+            return;
+        }
+        let (sx, rx) = mpsc::sync_channel::<u64>(self.buffer_size(category));
+        let data = data_slot(data);
+        let worker_data = data.clone();
+        let reporter = self.reporter(category);
+        let state = Arc::new(SystemState::identified(reporter.id()));
+        let worker_state = state.clone();
+        let handle = self.spawn_thread(category, move || {
+            //sequences which arrived before one of their predecessors.
+            let mut early = BinaryHeap::new();
+            let mut next = 0;
+            while let Ok(seq) = rx.recv() {
+                worker_state.received();
+                early.push(Reverse(seq));
+                while early.peek() == Some(&Reverse(next)) {
+                    early.pop();
+                    let start = Instant::now();
+                    if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| f(worker_data.load_full(), next))) {
+                        reporter.panicked(payload.as_ref());
+                        worker_state.exited(ExitReason::Panicked);
+                        return;
+                    }
+                    worker_state.complete(start.elapsed());
+                    next += 1;
+                }
+            }
+            worker_state.exited(ExitReason::Normal);
+        });
+        let th = ThreadHandle {
+            thread_id: Some(handle.thread().id()),
+            join_handle: Some(handle),
+            mx: Some(Box::new(Sequencer { next: AtomicU64::new(0), sx })),
+            state,
+            data: Some(data),
+            ..Default::default()
+        };
+        self.push_handle(category, th);
+    }
+
+    /// Stamps the next sequence number of a sequenced system and sends it, blocking while its buffer is full.
+    /// Returns the sequence number it was stamped with.
+    pub fn signal_seq(&self, category: &str, index: usize) -> Result<u64, EcstaticError> {
+        let th = self.handle(category, index)?;
+        let sequencer = th.mx.as_ref()
+            .and_then(|mx| mx.downcast_ref::<Sequencer>())
+            .ok_or_else(|| EcstaticError::TypeMismatch { category: String::from(category), index })?;
+        let seq = sequencer.next.fetch_add(1, Ordering::SeqCst);
+        sequencer.sx.send(seq).map_err(|_| EcstaticError::Disconnected { category: String::from(category), index })?;
+        th.state.signaled();
+        Ok(seq)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    use crate::test_util::assert_invocations;
+    use crate::{EcstaticError, EcstaticSystems};
+
+    static OBSERVED: Mutex<Vec<u64>> = Mutex::new(Vec::new());
+
+    fn observe(_: Arc<&AtomicUsize>, seq: u64) {
+        OBSERVED.lock().unwrap().push(seq);
+    }
+
+    #[test]
+    fn test_signal_seq() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_sequenced("testing", &ATOMIC, observe);
+        sys.register_static("plain", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        thread::scope(|s| {
+            for _ in 0..2 {
+                s.spawn(|| for _ in 0..200 {
+                    sys.signal_seq("testing", 0).unwrap();
+                });
+            }
+        });
+        assert!(sys.signal_seq("plain", 0) == Err(EcstaticError::TypeMismatch { category: String::from("plain"), index: 0 }));
+        assert_invocations(&sys, "testing", 0, 400);
+        sys.clear();
+
+        let observed = OBSERVED.lock().unwrap().clone();
+        assert!(observed.windows(2).all(|pair| pair[0] < pair[1]), "Actual: {:?}", observed);
+        assert!(observed == (0..400).collect::<Vec<u64>>(), "Actual: {:?}", observed);
+    }
+}
@@ -0,0 +1,217 @@
+use std::any::Any;
+use std::marker::{Send, Sync};
+use std::sync::{Arc, RwLock};
+
+use super::{EcstaticError, EcstaticSystems};
+
+/// A cloneable handle to a set of systems which can be shared between producer threads.
+///
+/// Signaling only takes a read lock, so any number of threads may signal concurrently.
+/// Registering and joining take the write lock and are serialized against everything else.
+#[derive(Clone, Default)]
+pub struct SharedSystems {
+    inner: Arc<RwLock<EcstaticSystems>>,
+}
+
+impl SharedSystems {
+    pub fn new() -> SharedSystems {
+        SharedSystems::from(EcstaticSystems::new())
+    }
+
+    /// Sends a signal to every possible thread handle amongst all categories.
+    pub fn signal_all(&self) {
+        self.inner.read().unwrap().signal_all();
+    }
+
+    /// Sends a signal to every thread handle in a category.
+    pub fn signal(&self, category: &str) {
+        self.inner.read().unwrap().signal(category);
+    }
+
+    /// Signals every handle in a category exactly once, see `EcstaticSystems::signal_round`.
+    /// Systems may call this on their own category and get an error instead of blocking forever.
+    pub fn signal_round(&self, category: &str) -> Result<usize, EcstaticError> {
+        self.inner.read().unwrap().signal_round(category)
+    }
+
+    /// Registers a system which will run on its own thread, but only operates when given a signal through its sender.
+    pub fn register_static<'a: 'static, T: Any + Send + Sync>(&self, category: &str, data: &'a T, f: fn(Arc<&'a T>)) {
+        self.inner.write().unwrap().register_static(category, data, f);
+    }
+
+    /// Drops the senders for a thread category and joins each thread in the category.
+    pub fn drop_join_category(&self, category: &str) {
+        self.inner.write().unwrap().drop_join_category(category);
+    }
+
+    /// Shuts the systems down like `EcstaticSystems::begin_shutdown`, except that signals still buffered are dropped
+    /// rather than run. This releases producers blocked on a full buffer in `signal`, `signal_all` or `signal_round`,
+    /// which would otherwise hold the lock needed to join the workers forever. Only `signal_round` can tell them
+    /// apart from a delivered signal, by returning `EcstaticError::ShuttingDown`.
+    pub fn begin_shutdown(&self) {
+        self.inner.read().unwrap().interrupt_signals();
+        self.inner.write().unwrap().begin_shutdown();
+    }
+}
+
+impl From<EcstaticSystems> for SharedSystems {
+    fn from(systems: EcstaticSystems) -> SharedSystems {
+        SharedSystems {
+            inner: Arc::new(RwLock::new(systems)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Mutex, OnceLock};
+    use std::thread;
+    use std::time::Instant;
+
+    use super::SharedSystems;
+    use crate::test_util::{gated, Gated, DEFAULT_TIMEOUT};
+    use crate::{CategoryConfig, CollectingSink, EcstaticError, EcstaticSystems, ErrorEvent};
+
+    #[test]
+    fn test_concurrent_producers() {
+        let shared = SharedSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        shared.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        shared.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+
+        let producers: Vec<_> = (0..4).map(|_| {
+            let sys = shared.clone();
+            thread::spawn(move || {
+                for _ in 0..25 {
+                    sys.signal("testing");
+                }
+            })
+        }).collect();
+        for p in producers {
+            p.join().unwrap();
+        }
+        shared.drop_join_category("testing");
+
+        assert!(ATOMIC.load(Ordering::Relaxed) == 200, "Actual: {} ; Expected: {}", ATOMIC.load(Ordering::Relaxed), 200);
+    }
+
+    #[test]
+    fn test_signal_all_while_registering() {
+        let shared = SharedSystems::new();
+        static COUNTS: [AtomicUsize; 8] = [const { AtomicUsize::new(0) }; 8];
+        static CHURN: AtomicUsize = AtomicUsize::new(0);
+
+        for (i, count) in COUNTS.iter().enumerate() {
+            shared.register_static(&format!("c{}", i), count, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        }
+        let producers: Vec<_> = (0..4).map(|_| {
+            let sys = shared.clone();
+            thread::spawn(move || {
+                for _ in 0..25 {
+                    sys.signal_all();
+                }
+            })
+        }).collect();
+        let churn = shared.clone();
+        let registering = thread::spawn(move || {
+            for _ in 0..20 {
+                churn.register_static("churn", &CHURN, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+                churn.drop_join_category("churn");
+            }
+        });
+        for p in producers {
+            p.join().unwrap();
+        }
+        registering.join().unwrap();
+        for i in 0..COUNTS.len() {
+            shared.drop_join_category(&format!("c{}", i));
+        }
+
+        let counts: Vec<usize> = COUNTS.iter().map(|count| count.load(Ordering::SeqCst)).collect();
+        assert!(counts.iter().all(|count| *count == 100), "Actual: {:?}", counts);
+        assert!(CHURN.load(Ordering::SeqCst) <= 100);
+    }
+
+    #[test]
+    fn test_begin_shutdown_releases_blocked_producer() {
+        static GATED: Gated = Gated::new();
+        let mut sys = EcstaticSystems::new();
+        sys.configure_category("testing", CategoryConfig { buffer_size: Some(1), ..Default::default() });
+        sys.register_static("testing", &GATED, gated);
+        static LIFO: Gated = Gated::new();
+        sys.configure_category("lifo", CategoryConfig { buffer_size: Some(1), ..Default::default() });
+        sys.register_lifo("lifo", &LIFO, |x, _| {
+            x.runs.fetch_add(1, Ordering::SeqCst);
+            while !x.released.load(Ordering::SeqCst) {
+                thread::yield_now();
+            }
+        });
+        let state = sys.handles["testing"][0].state.clone();
+        let lifo_state = sys.handles["lifo"][0].state.clone();
+        let shared = SharedSystems::from(sys);
+
+        shared.signal_round("testing").unwrap();
+        shared.signal("lifo");
+        let start = Instant::now();
+        while GATED.runs.load(Ordering::SeqCst) == 0 || LIFO.runs.load(Ordering::SeqCst) == 0 {
+            assert!(start.elapsed() < DEFAULT_TIMEOUT, "worker never started");
+            thread::yield_now();
+        }
+        shared.signal_round("testing").unwrap();
+        shared.signal("lifo");
+        let blocked = shared.clone();
+        let producer = thread::spawn(move || blocked.signal_round("testing"));
+        let blocked = shared.clone();
+        let lifo_producer = thread::spawn(move || blocked.signal("lifo"));
+        let stopping = shared.clone();
+        let shutdown = thread::spawn(move || stopping.begin_shutdown());
+        while !state.is_disabled() || !lifo_state.is_disabled() {
+            assert!(start.elapsed() < DEFAULT_TIMEOUT, "shutdown never began");
+            thread::yield_now();
+        }
+        GATED.released.store(true, Ordering::SeqCst);
+        LIFO.released.store(true, Ordering::SeqCst);
+
+        let result = producer.join().unwrap();
+        assert!(result == Err(EcstaticError::ShuttingDown), "Actual: {:?}", result);
+        lifo_producer.join().unwrap();
+        shutdown.join().unwrap();
+        assert!(LIFO.runs.load(Ordering::SeqCst) == 1, "Actual: {:?}", LIFO.runs.load(Ordering::SeqCst));
+        assert!(shared.signal_round("testing") == Err(EcstaticError::ShuttingDown));
+        assert!(GATED.runs.load(Ordering::SeqCst) == 1, "Actual: {:?}", GATED.runs.load(Ordering::SeqCst));
+    }
+
+    static SHARED: OnceLock<SharedSystems> = OnceLock::new();
+
+    #[test]
+    fn test_reentrant_signal() {
+        static RESULTS: Mutex<Vec<Result<usize, EcstaticError>>> = Mutex::new(Vec::new());
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        let sink = CollectingSink::new();
+        let mut sys = EcstaticSystems::builder().error_sink(sink.clone()).build();
+        sys.configure_category("round", CategoryConfig { buffer_size: Some(0), ..Default::default() });
+        sys.configure_category("plain", CategoryConfig { buffer_size: Some(0), ..Default::default() });
+        let shared = SHARED.get_or_init(|| SharedSystems::from(sys));
+
+        shared.register_static("round", &RESULTS, |results| results.lock().unwrap().push(SHARED.get().unwrap().signal_round("round")));
+        shared.register_static("plain", &ATOMIC, |x| {
+            SHARED.get().unwrap().signal("plain");
+            x.fetch_add(1, Ordering::SeqCst);
+        });
+        shared.signal("round");
+        shared.signal("plain");
+        let deadline = Instant::now() + DEFAULT_TIMEOUT;
+        while (RESULTS.lock().unwrap().is_empty() || ATOMIC.load(Ordering::SeqCst) == 0) && Instant::now() < deadline {
+            thread::yield_now();
+        }
+
+        let results = RESULTS.lock().unwrap().clone();
+        assert!(results == vec![Err(EcstaticError::Reentrancy { category: String::from("round"), index: 0 })], "Actual: {:?}", results);
+        assert!(sink.events() == vec![ErrorEvent::SignalDropped { category: String::from("plain"), index: 0 }], "Actual: {:?}", sink.events());
+        shared.drop_join_category("round");
+        shared.drop_join_category("plain");
+        assert!(ATOMIC.load(Ordering::SeqCst) == 1);
+    }
+}
@@ -0,0 +1,78 @@
+use std::any::Any;
+use std::marker::{Send, Sync};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use super::{EcstaticError, EcstaticSystems};
+
+impl EcstaticSystems {
+    /// Stops the manager from accepting systems and then joins every category like `clear`. The `register_*` methods
+    /// do nothing from then on, without spawning a thread or running any `init`, while `try_register_static`,
+    /// `create_category`, `respawn_category` and `signal_round` return `EcstaticError::ShuttingDown` instead.
+    pub fn begin_shutdown(&mut self) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+        self.clear();
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
+    /// Marks the manager as shutting down and makes every signaled worker, including LIFO workers, drop its buffered
+    /// signals instead of running its system. This releases producers blocked in `signal`, `signal_all` or
+    /// `signal_round` on a full buffer, the only sends `SharedSystems` offers. An invocation which is already running
+    /// is not interrupted. Pools, receivers, responders and sequenced workers keep running what they are sent.
+    pub(crate) fn interrupt_signals(&self) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+        for th in self.handles.values().flatten() {
+            th.state.disable();
+        }
+    }
+
+    /// Registers a system like `register_static`, unless the manager is shutting down.
+    pub fn try_register_static<'a: 'static, T: Any + Send + Sync>(&mut self, category: &str, data: &'a T, f: fn(Arc<&'a T>)) -> Result<(), EcstaticError> {
+        if self.is_shutting_down() {
+            return Err(EcstaticError::ShuttingDown);
+        }
+        self.register_static(category, data, f);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::test_util::{gated, pump_until, Gated};
+    use crate::{EcstaticError, EcstaticSystems};
+
+    #[test]
+    fn test_begin_shutdown() {
+        let mut sys = EcstaticSystems::new();
+        static GATED: Gated = Gated::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("testing", &GATED, gated);
+        for _ in 0..3 {
+            sys.signal("testing");
+        }
+        pump_until(&sys, |_| GATED.runs.load(Ordering::SeqCst) == 1);
+        GATED.released.store(true, Ordering::SeqCst);
+        sys.begin_shutdown();
+        assert!(sys.is_shutting_down());
+        assert!(GATED.runs.load(Ordering::SeqCst) == 3);
+        assert!(!sys.has_category("testing"));
+
+        assert!(sys.try_register_static("late", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); }) == Err(EcstaticError::ShuttingDown));
+        sys.register_static("late", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_worker_pool("late", &ATOMIC, |x, _: usize|{ x.fetch_add(1, Ordering::SeqCst); }, 4);
+        sys.register_stateful("late", || -> usize { panic!("init ran during shutdown") }, |_| (), |_| ());
+        assert!(sys.register_many::<AtomicUsize>("late", Vec::new()).is_empty());
+        assert!(sys.create_category("late") == Err(EcstaticError::ShuttingDown));
+        assert!(sys.respawn_category("late") == Err(EcstaticError::ShuttingDown));
+        assert!(!sys.has_category("late"));
+        assert!(sys.lifetime_spawn_count() == 1 && sys.lifetime_join_count() == 1);
+        sys.clear();
+        assert!(ATOMIC.load(Ordering::SeqCst) == 0);
+    }
+}
@@ -0,0 +1,283 @@
+use std::any::Any;
+use std::mem;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use super::state::HandleId;
+use super::{EcstaticSystems, EcstaticSystemsBuilder};
+
+/// A failure observed in a running system.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorEvent {
+    /// A system function panicked and its thread stopped.
+    Panicked { category: String, index: usize, message: String },
+    /// Signaling a handle blocked for longer than the watchdog threshold, see `EcstaticSystemsBuilder::signal_watchdog`.
+    /// The signal is still delivered once the handle has room for it.
+    SignalBlocked { category: String, index: usize, waited: Duration },
+    /// A signal was dropped because the handle's buffer was full and blocking would never return, since nothing
+    /// drains the buffer while the sender waits. This happens to handles without a thread of their own, and to
+    /// workers which `signal` their own full handle.
+    SignalDropped { category: String, index: usize },
+    /// The manager was dropped while these categories still had running workers, which were joined by the drop.
+    /// Only raised in debug builds, to catch teardowns which were forgotten.
+    Leaked { categories: Vec<String> },
+    /// `signal_graph` was about to signal a category while categories of a lower `SchedulerClass` which it has to
+    /// wait for were still running. `bypassed` is whether the category was signaled without waiting for them,
+    /// see `EcstaticSystemsBuilder::priority_bypass`.
+    PriorityInversion { category: String, blocked_by: Vec<String>, bypassed: bool },
+}
+
+/// Receives every `ErrorEvent` raised by a manager's systems. Events are reported from the
+/// thread where they happened, so implementations must be thread safe.
+/// Any `Fn(&ErrorEvent)` closure can be used as a sink.
+pub trait ErrorSink: Send + Sync {
+    fn report(&self, event: &ErrorEvent);
+}
+
+impl<F: Fn(&ErrorEvent) + Send + Sync> ErrorSink for F {
+    fn report(&self, event: &ErrorEvent) {
+        self(event)
+    }
+}
+
+/// An `ErrorSink` which keeps every event it receives. Clones share the same events.
+#[derive(Clone, Default)]
+pub struct CollectingSink {
+    events: Arc<Mutex<Vec<ErrorEvent>>>,
+}
+
+impl CollectingSink {
+    pub fn new() -> CollectingSink {
+        CollectingSink::default()
+    }
+
+    /// A copy of every event received so far.
+    pub fn events(&self) -> Vec<ErrorEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /// Removes and returns every event received so far.
+    pub fn take(&self) -> Vec<ErrorEvent> {
+        mem::take(&mut *self.events.lock().unwrap())
+    }
+}
+
+impl ErrorSink for CollectingSink {
+    fn report(&self, event: &ErrorEvent) {
+        self.events.lock().unwrap().push(event.clone());
+    }
+}
+
+/// Turns a panic payload of one known type into a message, see `EcstaticSystems::register_panic_downcast`.
+type PanicDowncast = Box<dyn Fn(&(dyn Any + Send)) -> Option<String> + Send + Sync>;
+
+/// The downcasts registered with a manager, shared with the reporters of its handles.
+pub(crate) type PanicDowncasts = Arc<RwLock<Vec<PanicDowncast>>>;
+
+/// Reports failures of one handle to the manager's sink, if it has one.
+#[derive(Clone)]
+pub(crate) struct Reporter {
+    sink: Option<Arc<dyn ErrorSink>>,
+    downcasts: PanicDowncasts,
+    id: HandleId,
+}
+
+impl Reporter {
+    pub(crate) fn id(&self) -> HandleId {
+        self.id.clone()
+    }
+
+    pub(crate) fn panicked(&self, payload: &(dyn Any + Send)) {
+        let (category, index) = self.id.get().unwrap_or_default();
+        #[cfg(feature = "metrics")]
+        crate::telemetry::Labels::new(&category, index).panicked();
+        if let Some(sink) = self.sink.as_ref() {
+            sink.report(&ErrorEvent::Panicked {
+                category,
+                index,
+                message: panic_message(&self.downcasts, payload),
+            });
+        }
+    }
+}
+
+fn panic_message(downcasts: &PanicDowncasts, payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = downcasts.read().unwrap().iter().find_map(|downcast| downcast(payload)) {
+        message
+    } else if let Some(message) = payload.downcast_ref::<&str>() {
+        String::from(*message)
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("Box<dyn Any>")
+    }
+}
+
+impl EcstaticSystemsBuilder {
+    /// Sets the sink which receives the errors of every system in the manager.
+    pub fn error_sink<S: ErrorSink + 'static>(mut self, sink: S) -> EcstaticSystemsBuilder {
+        self.error_sink = Some(Arc::new(sink));
+        self
+    }
+}
+
+impl EcstaticSystems {
+    /// Describes panic payloads of type `E` with `f` in the `ErrorEvent::Panicked` events sent to the sink,
+    /// including for systems registered before this call. Downcasts are tried in the order they were registered,
+    /// ahead of the built in messages for `&str` and `String` payloads.
+    pub fn register_panic_downcast<E: Any, F: Fn(&E) -> String + Send + Sync + 'static>(&mut self, f: F) {
+        self.panic_downcasts.write().unwrap().push(Box::new(move |payload| payload.downcast_ref::<E>().map(&f)));
+    }
+
+    /// Reports the categories which still have running workers as `ErrorEvent::Leaked`, or prints them when
+    /// there is no sink. Does nothing when every worker has been joined.
+    #[cfg(debug_assertions)]
+    pub(crate) fn report_leaked(&self) {
+        let categories: Vec<String> = self.order.iter()
+            .filter(|category| self.handles[*category].iter().any(|th| th.join_handle.is_some()))
+            .cloned()
+            .collect();
+        if categories.is_empty() {
+            return;
+        }
+        match self.error_sink.as_ref() {
+            Some(sink) => sink.report(&ErrorEvent::Leaked { categories }),
+            None => eprintln!("ecstatic_system: manager dropped with running systems in {:?}, joining them now", categories),
+        }
+    }
+
+    /// A reporter for the next handle to be pushed into a category. It follows the handle when it moves as long as
+    /// the handle's state is created with `SystemState::identified(reporter.id())`.
+    pub(crate) fn reporter(&self, category: &str) -> Reporter {
+        Reporter {
+            sink: self.error_sink.clone(),
+            downcasts: self.panic_downcasts.clone(),
+            id: HandleId::new(category, self.handles.get(category).map_or(0, Vec::len)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt;
+    use std::panic;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    use super::{CollectingSink, ErrorEvent};
+    use crate::test_util::pump_until;
+    use crate::EcstaticSystems;
+
+    #[test]
+    fn test_custom_sink_receives_panic() {
+        static EVENTS: Mutex<Vec<ErrorEvent>> = Mutex::new(Vec::new());
+        let mut sys = EcstaticSystems::builder()
+            .error_sink(|e: &ErrorEvent| EVENTS.lock().unwrap().push(e.clone()))
+            .build();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("testing", &ATOMIC, |_| panic!("boom"));
+        sys.signal("testing");
+        pump_until(&sys, |_| !EVENTS.lock().unwrap().is_empty());
+        sys.drop_join_category("testing");
+
+        let events = EVENTS.lock().unwrap().clone();
+        assert!(events == vec![ErrorEvent::Panicked { category: String::from("testing"), index: 1, message: String::from("boom") }], "Actual: {:?}", events);
+        assert!(ATOMIC.load(Ordering::Relaxed) == 1);
+    }
+
+    #[test]
+    fn test_collecting_sink() {
+        let sink = CollectingSink::new();
+        let mut sys = EcstaticSystems::builder().error_sink(sink.clone()).build();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_worker_pool("pool", &ATOMIC, |_, job: usize| panic!("job {} failed", job), 1);
+        sys.register_responder("responder", &ATOMIC, |_, _: usize| -> usize { panic!("no reply") });
+        sys.submit_job("pool", 7usize).unwrap();
+        assert!(sys.request::<usize, usize>("responder", 0, 1).is_err());
+        pump_until(&sys, |_| sink.events().len() == 2);
+        sys.clear();
+
+        let mut events = sink.take();
+        events.sort_by_key(|e| match e {
+            ErrorEvent::Panicked { category, .. } => category.clone(),
+            ErrorEvent::SignalBlocked { category, .. } => category.clone(),
+            ErrorEvent::SignalDropped { category, .. } => category.clone(),
+            ErrorEvent::Leaked { .. } => String::new(),
+            ErrorEvent::PriorityInversion { category, .. } => category.clone(),
+        });
+        assert!(events == vec![
+            ErrorEvent::Panicked { category: String::from("pool"), index: 0, message: String::from("job 7 failed") },
+            ErrorEvent::Panicked { category: String::from("responder"), index: 0, message: String::from("no reply") },
+        ], "Actual: {:?}", events);
+        assert!(sink.events().is_empty());
+    }
+
+    #[derive(Debug)]
+    struct LoadError {
+        path: &'static str,
+    }
+
+    impl fmt::Display for LoadError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "failed to load {}", self.path)
+        }
+    }
+
+    #[test]
+    fn test_register_panic_downcast() {
+        let sink = CollectingSink::new();
+        let mut sys = EcstaticSystems::builder().error_sink(sink.clone()).build();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("testing", &ATOMIC, |_| panic::panic_any(LoadError { path: "assets/map.bin" }));
+        sys.register_static("testing", &ATOMIC, |_| panic::panic_any(7u32));
+        sys.register_static("testing", &ATOMIC, |_| panic!("boom"));
+        sys.register_panic_downcast(|e: &LoadError| e.to_string());
+        sys.signal("testing");
+        pump_until(&sys, |_| sink.events().len() == 3);
+        sys.drop_join_category("testing");
+
+        let mut events = sink.take();
+        events.sort_by_key(|e| match e {
+            ErrorEvent::Panicked { index, .. } => *index,
+            _ => usize::MAX,
+        });
+        let panicked = |index, message: &str| ErrorEvent::Panicked { category: String::from("testing"), index, message: String::from(message) };
+        assert!(events == vec![
+            panicked(0, "failed to load assets/map.bin"),
+            panicked(1, "Box<dyn Any>"),
+            panicked(2, "boom"),
+        ], "Actual: {:?}", events);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_leaked_on_drop() {
+        static EVENTS: Mutex<Vec<ErrorEvent>> = Mutex::new(Vec::new());
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        let builder = || EcstaticSystems::builder().error_sink(|e: &ErrorEvent| EVENTS.lock().unwrap().push(e.clone()));
+
+        let mut sys = builder().build();
+        sys.register_static("cleared", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.create_category("empty").unwrap();
+        sys.clear();
+        drop(sys);
+        assert!(EVENTS.lock().unwrap().is_empty());
+
+        let mut sys = builder().build();
+        sys.register_static("first", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("joined", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("second", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.create_category("empty").unwrap();
+        sys.drop_join_category("joined");
+        sys.signal_all();
+        drop(sys);
+
+        let events = EVENTS.lock().unwrap().clone();
+        assert!(events == vec![ErrorEvent::Leaked { categories: vec![String::from("first"), String::from("second")] }], "Actual: {:?}", events);
+        assert!(ATOMIC.load(Ordering::SeqCst) == 2, "Actual: {:?}", ATOMIC.load(Ordering::SeqCst));
+    }
+}
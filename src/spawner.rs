@@ -0,0 +1,72 @@
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use super::{EcstaticSystems, EcstaticSystemsBuilder};
+
+/// Launches a worker thread running the given closure, see `EcstaticSystemsBuilder::spawner`.
+pub type Spawner = Arc<dyn Fn(Box<dyn FnOnce() + Send>) -> JoinHandle<()> + Send + Sync>;
+
+impl EcstaticSystemsBuilder {
+    /// Launches every worker, timer and monitor thread through `spawner` instead of `thread::Builder`, e.g. to name
+    /// threads or hand them to a runtime. The returned handle must finish once the closure returns, and categories' stack sizes are not applied.
+    pub fn spawner(mut self, spawner: Spawner) -> EcstaticSystemsBuilder {
+        self.spawner = Some(spawner);
+        self
+    }
+}
+
+impl EcstaticSystems {
+    /// Creates a manager which launches every worker through `spawner`, see `EcstaticSystemsBuilder::spawner`.
+    pub fn with_spawner(spawner: Spawner) -> EcstaticSystems {
+        EcstaticSystems::builder().spawner(spawner).build()
+    }
+
+    /// Spawns a thread which serves the manager rather than a category, through the spawner if there is one.
+    /// Unlike workers it is not counted by `lifetime_spawn_count`, pinned to a core or given a category's stack size.
+    pub(crate) fn spawn_helper<F: FnOnce() + Send + 'static>(&self, f: F) -> JoinHandle<()> {
+        match self.spawner.as_ref() {
+            Some(spawner) => spawner(Box::new(f)),
+            None => thread::spawn(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::test_util::assert_invocations;
+    use crate::EcstaticSystems;
+
+    #[test]
+    fn test_custom_spawner() {
+        static LAUNCHED: AtomicUsize = AtomicUsize::new(0);
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        static NAMES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        let mut sys = EcstaticSystems::with_spawner(Arc::new(|f| {
+            let id = LAUNCHED.fetch_add(1, Ordering::SeqCst);
+            thread::Builder::new().name(format!("custom-{}", id)).spawn(f).unwrap()
+        }));
+
+        sys.register_static("testing", &NAMES, |names| names.lock().unwrap().push(String::from(thread::current().name().unwrap())));
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_worker_pool("pool", &ATOMIC, |x, job: usize| { x.fetch_add(job, Ordering::SeqCst); }, 2);
+        assert!(LAUNCHED.load(Ordering::SeqCst) == 4);
+        let timer = sys.schedule("pool", Duration::from_secs(60)).unwrap();
+        assert!(LAUNCHED.load(Ordering::SeqCst) == 5);
+        assert!(sys.cancel_schedule(timer.id()));
+        sys.on_slow_consumer(usize::MAX, Duration::from_secs(60), |_| ());
+        assert!(LAUNCHED.load(Ordering::SeqCst) == 6);
+
+        sys.signal("testing");
+        sys.submit_job("pool", 10usize).unwrap();
+        assert_invocations(&sys, "testing", 0, 1);
+        assert_invocations(&sys, "testing", 1, 1);
+        sys.clear();
+        assert!(*NAMES.lock().unwrap() == vec![String::from("custom-0")]);
+        assert!(ATOMIC.load(Ordering::SeqCst) == 11);
+    }
+}
@@ -0,0 +1,238 @@
+use std::any::Any;
+use std::marker::{Send, Sync};
+use std::mem;
+use std::sync::Arc;
+
+use super::{join_handles, EcstaticError, EcstaticSystems, ExitReason, ThreadHandle};
+
+/// Spawns a fresh worker for the same system as the handle it is kept on, see `respawn_category`.
+pub(crate) type Respawn = Arc<dyn Fn(&EcstaticSystems, &str) -> ThreadHandle + Send + Sync>;
+
+/// The data and function of a static system, bundled so that systems can be described up front
+/// and registered together.
+pub struct SystemSpec<T: 'static> {
+    pub data: &'static T,
+    pub f: fn(Arc<&'static T>),
+}
+
+impl<T: 'static> SystemSpec<T> {
+    pub fn new(data: &'static T, f: fn(Arc<&'static T>)) -> SystemSpec<T> {
+        SystemSpec { data, f }
+    }
+}
+
+impl<T: 'static> Clone for SystemSpec<T> {
+    fn clone(&self) -> SystemSpec<T> {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for SystemSpec<T> {}
+
+impl EcstaticSystems {
+    /// Registers every spec as a static system in a category.
+    /// Returns the index of each new system within the category, in the same order as `specs`.
+    pub fn register_many<T: Any + Send + Sync>(&mut self, category: &str, specs: Vec<SystemSpec<T>>) -> Vec<usize> {
+        if self.is_shutting_down() {
+            return Vec::new();
+        }
+        self.lazy_init_category(category);
+        let first = self.handles[category].len();
+        for spec in specs {
+            let th = self.static_system_create(category, spec.data, spec.f);
+            self.push_handle(category, th);
+        }
+        (first..self.handles[category].len()).collect()
+    }
+
+    /// Registers one system per function in a category, all sharing the same `data`.
+    /// Returns the index of each new system within the category, in the same order as `fs`.
+    pub fn register_shared<T: Send + Sync + 'static>(&mut self, category: &str, data: Arc<T>, fs: Vec<fn(Arc<T>)>) -> Vec<usize> {
+        if self.is_shutting_down() {
+            return Vec::new();
+        }
+        self.lazy_init_category(category);
+        let first = self.handles[category].len();
+        for f in fs {
+            let worker_data = data.clone();
+            let th = self.system_create(category, move || f(worker_data.clone()));
+            self.push_handle(category, th);
+        }
+        (first..self.handles[category].len()).collect()
+    }
+
+    /// Registers the same static system once in each of `categories`, so every category gets its own handle.
+    pub fn register_broadcast<'a: 'static, T: Any + Send + Sync>(&mut self, categories: &[&str], data: &'a T, f: fn(Arc<&'a T>)) {
+        for category in categories {
+            self.register_static(category, data, f);
+        }
+    }
+
+    /// Replaces every system in a category with `specs`, spawning the new systems before joining the old ones.
+    /// Timers created by `schedule` for the category are cancelled. Returns why each old worker stopped, like `exit_reason`.
+    pub fn swap_category<T: Any + Send + Sync>(&mut self, category: &str, specs: Vec<SystemSpec<T>>) -> Vec<Option<ExitReason>> {
+        self.cancel_category_schedules(category);
+        self.lazy_init_category(category);
+        let mut old = mem::take(self.handles.get_mut(category).unwrap());
+        self.register_many(category, specs);
+        join_handles(&mut old, &self.joined)
+    }
+
+    /// Joins every handle in a category, then registers a fresh worker for each one, using the data it had when joined.
+    /// Only handles registered with `register_static` or from a `SystemSpec` can be respawned. If any other handle is in
+    /// the category, `EcstaticError::Unsupported` is returned for the first of them and nothing is joined.
+    /// Timers created by `schedule` for the category are cancelled. Returns how many workers were respawned.
+    pub fn respawn_category(&mut self, category: &str) -> Result<usize, EcstaticError> {
+        if self.is_shutting_down() {
+            return Err(EcstaticError::ShuttingDown);
+        }
+        let ths = self.handles.get(category).ok_or_else(|| EcstaticError::UnknownCategory(String::from(category)))?;
+        if let Some(index) = ths.iter().position(|th| th.respawn.is_none()) {
+            return Err(EcstaticError::Unsupported { category: String::from(category), index });
+        }
+        let mut old = mem::take(self.handles.get_mut(category).unwrap());
+        self.cancel_category_schedules(category);
+        let respawns: Vec<Respawn> = old.iter().filter_map(|th| th.respawn.clone()).collect();
+        join_handles(&mut old, &self.joined);
+        for respawn in respawns.iter() {
+            let th = respawn(self, category);
+            self.push_handle(category, th);
+        }
+        Ok(respawns.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::SystemSpec;
+    use crate::test_util::{assert_invocations, pump_until};
+    use crate::{EcstaticError, EcstaticSystems, ExitReason, HandleStatus};
+
+    #[test]
+    fn test_register_many() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMICS: [AtomicUsize; 5] = [AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0)];
+
+        sys.register_static("testing", &ATOMICS[0], |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        let specs = ATOMICS.iter().map(|a| SystemSpec::new(a, |x|{ x.fetch_add(1, Ordering::SeqCst); })).collect();
+        let ids = sys.register_many("testing", specs);
+        assert!(ids == vec![1, 2, 3, 4, 5], "Actual: {:?}", ids);
+
+        sys.signal("testing");
+        sys.drop_join_category("testing");
+
+        assert!(ATOMICS[0].load(Ordering::Relaxed) == 2);
+        for a in ATOMICS[1..].iter() {
+            assert!(a.load(Ordering::Relaxed) == 1);
+        }
+    }
+
+    #[test]
+    fn test_swap_category() {
+        let mut sys = EcstaticSystems::new();
+        static OLD: AtomicUsize = AtomicUsize::new(0);
+        static NEW: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("testing", &OLD, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_self_terminating("testing", &OLD, |_| false);
+        sys.signal("testing");
+        let old_threads = [sys.thread_id("testing", 0), sys.thread_id("testing", 1)];
+
+        let specs = vec![SystemSpec::new(&NEW, |x|{ x.fetch_add(1, Ordering::SeqCst); }); 3];
+        let reasons = sys.swap_category("testing", specs);
+        assert!(reasons == vec![Some(ExitReason::Normal), Some(ExitReason::SelfTerminated)], "Actual: {:?}", reasons);
+        assert!(OLD.load(Ordering::SeqCst) == 1);
+        assert!(sys.handles["testing"].len() == 3);
+        assert!((0..3).all(|index| !old_threads.contains(&sys.thread_id("testing", index))));
+
+        sys.signal("testing");
+        for index in 0..3 {
+            assert_invocations(&sys, "testing", index, 1);
+        }
+        sys.clear();
+        assert!(OLD.load(Ordering::SeqCst) == 1);
+        assert!(NEW.load(Ordering::SeqCst) == 3);
+    }
+
+    #[test]
+    fn test_register_broadcast() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        let categories = ["physics", "render", "audio"];
+
+        sys.register_broadcast(&categories, &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        for category in categories {
+            assert!(sys.handles(category).map(<[_]>::len) == Some(1), "Actual: {:?}", sys.handles(category).map(<[_]>::len));
+        }
+        sys.signal_all();
+        for category in categories {
+            assert_invocations(&sys, category, 0, 1);
+        }
+        sys.clear();
+
+        assert!(ATOMIC.load(Ordering::SeqCst) == 3, "Actual: {:?}", ATOMIC.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_register_shared() {
+        let mut sys = EcstaticSystems::new();
+        let data = Arc::new(AtomicUsize::new(0));
+
+        sys.register_shared("testing", data.clone(), vec![
+            |x: Arc<AtomicUsize>| { x.fetch_add(1, Ordering::SeqCst); },
+            |x: Arc<AtomicUsize>| { x.fetch_add(10, Ordering::SeqCst); },
+        ]);
+        let ids = sys.register_shared("testing", data.clone(), vec![|x: Arc<AtomicUsize>| { x.fetch_add(100, Ordering::SeqCst); }]);
+        assert!(ids == vec![2], "Actual: {:?}", ids);
+        assert!(Arc::strong_count(&data) == 4, "Actual: {:?}", Arc::strong_count(&data));
+        for _ in 0..2 {
+            sys.signal("testing");
+        }
+        for index in 0..3 {
+            assert_invocations(&sys, "testing", index, 2);
+        }
+        sys.clear();
+
+        assert!(data.load(Ordering::SeqCst) == 222, "Actual: {:?}", data.load(Ordering::SeqCst));
+        assert!(Arc::strong_count(&data) == 1);
+    }
+
+    #[test]
+    fn test_respawn_category() {
+        let mut sys = EcstaticSystems::new();
+        static FAILING: AtomicBool = AtomicBool::new(true);
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        static ATOMIC1: AtomicUsize = AtomicUsize::new(0);
+
+        fn flaky(x: Arc<&AtomicUsize>) {
+            if FAILING.load(Ordering::SeqCst) {
+                panic!("system failed");
+            }
+            x.fetch_add(1, Ordering::SeqCst);
+        }
+        sys.register_static("testing", &ATOMIC, flaky);
+        sys.register_many("testing", vec![SystemSpec::new(&ATOMIC1, flaky)]);
+        sys.register_stateful("testing", || 0, |_| panic!("system failed"), |_| ());
+        sys.signal("testing");
+        pump_until(&sys, |sys| (0..3).all(|index| sys.handle_status("testing", index) == Ok(HandleStatus::Exited)));
+
+        FAILING.store(false, Ordering::SeqCst);
+        assert!(sys.respawn_category("testing") == Err(EcstaticError::Unsupported { category: String::from("testing"), index: 2 }));
+        assert!(sys.handles("testing").map(<[_]>::len) == Some(3));
+        sys.move_handle("testing", 2, "stateful").unwrap();
+        assert!(sys.respawn_category("testing") == Ok(2));
+        assert!(sys.handles("testing").map(<[_]>::len) == Some(2));
+        sys.signal("testing");
+        for index in 0..2 {
+            assert!(sys.handle_status("testing", index) == Ok(HandleStatus::Running));
+            assert_invocations(&sys, "testing", index, 1);
+        }
+        assert!(sys.respawn_category("missing") == Err(EcstaticError::UnknownCategory(String::from("missing"))));
+        sys.clear();
+
+        assert!(ATOMIC.load(Ordering::SeqCst) == 1 && ATOMIC1.load(Ordering::SeqCst) == 1);
+    }
+}
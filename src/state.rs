@@ -0,0 +1,250 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use super::{ExitReason, SystemStats};
+
+/// The category and index a handle is registered under. Shared between its state and its reporter, so events
+/// and metrics follow the handle when it moves.
+#[derive(Clone, Default)]
+pub(crate) struct HandleId(Arc<RwLock<Option<(String, usize)>>>);
+
+impl HandleId {
+    pub(crate) fn new(category: &str, index: usize) -> HandleId {
+        let id = HandleId::default();
+        id.set(category, index);
+        id
+    }
+
+    pub(crate) fn set(&self, category: &str, index: usize) {
+        *self.0.write().unwrap() = Some((String::from(category), index));
+    }
+
+    pub(crate) fn get(&self) -> Option<(String, usize)> {
+        self.0.read().unwrap().clone()
+    }
+}
+
+/// Progress of a single system, shared between its handle and its worker thread.
+pub(crate) struct SystemState {
+    signaled: AtomicUsize,
+    received: AtomicUsize,
+    completed: Mutex<usize>,
+    invocations: AtomicUsize,
+    cv: Condvar,
+    total_nanos: AtomicU64,
+    min_nanos: AtomicU64,
+    max_nanos: AtomicU64,
+    exit: Mutex<Option<ExitReason>>,
+    disabled: AtomicBool,
+    panics: AtomicUsize,
+    acks: Mutex<Vec<(usize, Sender<()>)>>,
+    sample_rate: AtomicU32,
+    id: HandleId,
+    #[cfg(feature = "async")]
+    listeners: Mutex<Vec<crate::completion::Listener>>,
+}
+
+impl Default for SystemState {
+    fn default() -> SystemState {
+        SystemState {
+            signaled: AtomicUsize::new(0),
+            received: AtomicUsize::new(0),
+            completed: Mutex::new(0),
+            invocations: AtomicUsize::new(0),
+            cv: Condvar::new(),
+            total_nanos: AtomicU64::new(0),
+            min_nanos: AtomicU64::new(u64::MAX),
+            max_nanos: AtomicU64::new(0),
+            exit: Mutex::new(None),
+            disabled: AtomicBool::new(false),
+            panics: AtomicUsize::new(0),
+            acks: Mutex::new(Vec::new()),
+            sample_rate: AtomicU32::new(1),
+            id: HandleId::default(),
+            #[cfg(feature = "async")]
+            listeners: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl SystemState {
+    /// A state which shares its identity with a reporter, see `EcstaticSystems::reporter`.
+    pub(crate) fn identified(id: HandleId) -> SystemState {
+        SystemState { id, ..SystemState::default() }
+    }
+
+    /// Records a signal which was delivered to the worker and returns the total delivered so far.
+    pub(crate) fn signaled(&self) -> usize {
+        let signaled = self.signaled.fetch_add(1, Ordering::SeqCst) + 1;
+        #[cfg(feature = "metrics")]
+        self.emit(|labels| labels.queue_depth(self.pending()));
+        signaled
+    }
+
+    /// How many signals have been delivered to the worker in total.
+    pub(crate) fn signaled_count(&self) -> usize {
+        self.signaled.load(Ordering::SeqCst)
+    }
+
+    /// Records that the worker took a signal out of its buffer.
+    pub(crate) fn received(&self) {
+        self.received.fetch_add(1, Ordering::SeqCst);
+        #[cfg(feature = "metrics")]
+        self.emit(|labels| labels.queue_depth(self.pending()));
+    }
+
+    /// How many delivered signals are still waiting in the buffer.
+    pub(crate) fn pending(&self) -> usize {
+        self.signaled.load(Ordering::SeqCst).saturating_sub(self.received.load(Ordering::SeqCst))
+    }
+
+    /// How many signals the worker has finished processing.
+    pub(crate) fn completed(&self) -> usize {
+        *self.completed.lock().unwrap()
+    }
+
+    /// How many times the worker has run its system, unlike `completed` which also counts skipped signals.
+    pub(crate) fn invocations(&self) -> usize {
+        self.invocations.load(Ordering::SeqCst)
+    }
+
+    /// Whether the worker has finished processing every signal delivered to it.
+    #[cfg(test)]
+    pub(crate) fn idle(&self) -> bool {
+        self.completed() >= self.signaled.load(Ordering::SeqCst)
+    }
+
+    /// Records that the worker finished processing a signal, which took `run_time`.
+    pub(crate) fn complete(&self, run_time: Duration) {
+        let nanos = run_time.as_nanos().min(u64::MAX as u128) as u64;
+        self.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.min_nanos.fetch_min(nanos, Ordering::Relaxed);
+        self.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+        self.invocations.fetch_add(1, Ordering::SeqCst);
+        #[cfg(feature = "metrics")]
+        self.emit(|labels| labels.invoked(run_time));
+        self.skip();
+        #[cfg(feature = "async")]
+        self.listeners.lock().unwrap().retain(|listener| listener.completed());
+    }
+
+    /// Reports every invocation completed from now on to a `CompletionStream`.
+    #[cfg(feature = "async")]
+    pub(crate) fn listen(&self, listener: crate::completion::Listener) {
+        self.listeners.lock().unwrap().push(listener);
+    }
+
+    /// Records that the worker finished with a signal without running its system.
+    pub(crate) fn skip(&self) {
+        let mut completed = self.completed.lock().unwrap();
+        *completed += 1;
+        self.acks.lock().unwrap().retain(|(target, ack)| *target > *completed || ack.send(()).is_err());
+        self.cv.notify_all();
+    }
+
+    /// Returns a receiver which gets a message once `target` signals have completed.
+    /// It disconnects without a message if the worker exits first.
+    pub(crate) fn ack_at(&self, target: usize) -> Receiver<()> {
+        let (ack, acked) = mpsc::channel();
+        self.notify_at(target, ack);
+        acked
+    }
+
+    /// Sends a message through `ack` once `target` signals have completed, like `ack_at`.
+    /// The sender is dropped without a message if the worker exits first.
+    pub(crate) fn notify_at(&self, target: usize, ack: Sender<()>) {
+        let completed = self.completed.lock().unwrap();
+        if *completed >= target {
+            let _ = ack.send(());
+        } else if self.exit_reason().is_none() {
+            self.acks.lock().unwrap().push((target, ack));
+        }
+    }
+
+    /// Blocks until at least `target` signals have completed or the deadline passes.
+    /// Returns whether the target was reached.
+    pub(crate) fn wait_until(&self, target: usize, deadline: Instant) -> bool {
+        let mut completed = self.completed.lock().unwrap();
+        while *completed < target {
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            completed = self.cv.wait_timeout(completed, deadline - now).unwrap().0;
+        }
+        true
+    }
+
+    /// Records why the worker's loop ended.
+    pub(crate) fn exited(&self, reason: ExitReason) {
+        let _completed = self.completed.lock().unwrap();
+        *self.exit.lock().unwrap() = Some(reason);
+        self.acks.lock().unwrap().clear();
+    }
+
+    pub(crate) fn exit_reason(&self) -> Option<ExitReason> {
+        *self.exit.lock().unwrap()
+    }
+
+    /// Records that the worker's system panicked while processing a signal.
+    pub(crate) fn panicked(&self) {
+        self.panics.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// How many times the worker's system has panicked.
+    pub(crate) fn panics(&self) -> usize {
+        self.panics.load(Ordering::SeqCst)
+    }
+
+    /// Makes the worker drop signals without running its system, see `PanicPolicy::Disable`.
+    pub(crate) fn disable(&self) {
+        self.disabled.store(true, Ordering::SeqCst);
+    }
+
+    pub(crate) fn enable(&self) {
+        self.disabled.store(false, Ordering::SeqCst);
+    }
+
+    pub(crate) fn is_disabled(&self) -> bool {
+        self.disabled.load(Ordering::SeqCst)
+    }
+
+    /// Makes the worker run its system on only one in every `one_in` signals, see `set_sample_rate`.
+    pub(crate) fn set_sample_rate(&self, one_in: u32) {
+        self.sample_rate.store(one_in.max(1), Ordering::SeqCst);
+    }
+
+    pub(crate) fn sample_rate(&self) -> u32 {
+        self.sample_rate.load(Ordering::SeqCst)
+    }
+
+    /// Names the handle this state belongs to in the metrics it emits and in the events of its reporter.
+    /// Metrics are only emitted once it is named.
+    pub(crate) fn label(&self, category: &str, index: usize) {
+        self.id.set(category, index);
+    }
+
+    /// The category and index the handle this state belongs to is registered under, once it is named.
+    pub(crate) fn id(&self) -> Option<(String, usize)> {
+        self.id.get()
+    }
+
+    #[cfg(feature = "metrics")]
+    fn emit<F: FnOnce(&crate::telemetry::Labels)>(&self, f: F) {
+        if let Some((category, index)) = self.id.get() {
+            f(&crate::telemetry::Labels::new(&category, index));
+        }
+    }
+
+    pub(crate) fn stats(&self) -> SystemStats {
+        let count = self.invocations();
+        SystemStats {
+            count,
+            total_run_time: Duration::from_nanos(self.total_nanos.load(Ordering::Relaxed)),
+            min_run_time: if count == 0 { Duration::ZERO } else { Duration::from_nanos(self.min_nanos.load(Ordering::Relaxed)) },
+            max_run_time: Duration::from_nanos(self.max_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use super::EcstaticSystems;
+
+impl EcstaticSystems {
+    /// Registers a system which owns per-thread state for its whole lifetime.
+    /// `init` runs on the worker thread when it is spawned, `run` is called with the state for every signal,
+    /// and `teardown` receives the state on the worker thread once the category is dropped.
+    pub fn register_stateful<S: 'static>(&mut self, category: &str, init: fn() -> S, run: fn(&mut S), teardown: fn(S)) {
+        if self.is_shutting_down() {
+            return;
+        }
+        let th = self.system_create_with(category, init, move |s| {
+            run(s);
+            true
+        }, teardown);
+        self.push_handle(category, th);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    use crate::test_util::pump_until;
+    use crate::EcstaticSystems;
+
+    static INITED: AtomicBool = AtomicBool::new(false);
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    static DONE: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counter {
+        runs: usize,
+    }
+
+    #[test]
+    fn test_stateful_hooks() {
+        let mut sys = EcstaticSystems::new();
+
+        sys.register_stateful("testing",
+            || {
+                INITED.store(true, Ordering::SeqCst);
+                Counter { runs: 0 }
+            },
+            |c| {
+                c.runs += 1;
+                RUNS.store(c.runs, Ordering::SeqCst);
+            },
+            |c| DONE.store(c.runs, Ordering::SeqCst));
+        pump_until(&sys, |_| INITED.load(Ordering::SeqCst));
+
+        sys.signal("testing");
+        sys.signal("testing");
+        pump_until(&sys, |_| RUNS.load(Ordering::SeqCst) == 2);
+        assert!(DONE.load(Ordering::SeqCst) == 0);
+
+        sys.drop_join_category("testing");
+        assert!(DONE.load(Ordering::SeqCst) == 2);
+    }
+}
@@ -0,0 +1,182 @@
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use super::EcstaticSystems;
+
+/// A snapshot of how often a system has run and how long its invocations took.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SystemStats {
+    /// How many invocations have finished.
+    pub count: usize,
+    /// The combined duration of every finished invocation.
+    pub total_run_time: Duration,
+    /// The shortest invocation, or zero if the system has not run.
+    pub min_run_time: Duration,
+    /// The longest invocation, or zero if the system has not run.
+    pub max_run_time: Duration,
+}
+
+impl SystemStats {
+    /// The mean duration of an invocation, or zero if the system has not run.
+    pub fn average_run_time(&self) -> Duration {
+        mean(self.total_run_time, self.count)
+    }
+}
+
+/// `total` divided by `count`, or zero if `count` is zero. Divides in nanoseconds, since `Duration` can only be
+/// divided by a `u32`.
+pub(crate) fn mean(total: Duration, count: usize) -> Duration {
+    if count == 0 {
+        Duration::ZERO
+    } else {
+        Duration::from_nanos((total.as_nanos() / count as u128) as u64)
+    }
+}
+
+impl EcstaticSystems {
+    /// Invocation counts and timings for a handle.
+    pub fn stats(&self, category: &str, index: usize) -> Option<SystemStats> {
+        Some(self.handle(category, index).ok()?.state.stats())
+    }
+
+    /// Calls `f` with the stats of every handle, category by category in registration order.
+    pub fn for_each_handle<F: FnMut(&str, usize, SystemStats)>(&self, mut f: F) {
+        for category in self.order.iter() {
+            for (index, th) in self.handles[category].iter().enumerate() {
+                f(category, index, th.state.stats());
+            }
+        }
+    }
+
+    /// The combined run time of every finished invocation in a category, across all of its handles. Approximates the
+    /// CPU time the category consumed, counting time its systems spent blocked or sleeping as well.
+    /// Zero for a category which is not registered.
+    pub fn category_cpu_time(&self, category: &str) -> Duration {
+        self.handles.get(category)
+            .map(|ths| ths.iter().map(|th| th.state.stats().total_run_time).sum())
+            .unwrap_or_default()
+    }
+
+    /// How many worker threads this manager has spawned over its lifetime.
+    pub fn lifetime_spawn_count(&self) -> usize {
+        self.spawned.load(Ordering::Relaxed)
+    }
+
+    /// How many worker threads this manager has joined over its lifetime. Matches `lifetime_spawn_count` once every
+    /// category is joined, except for workers detached by `clear_timeout` or handed out by `into_handles`.
+    pub fn lifetime_join_count(&self) -> usize {
+        self.joined.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::SystemStats;
+    use crate::test_util::assert_invocations;
+    use crate::EcstaticSystems;
+
+    #[test]
+    fn test_run_time_stats() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("testing", &ATOMIC, |_| thread::sleep(Duration::from_millis(20)));
+        assert!(sys.stats("testing", 0).unwrap().average_run_time() == Duration::ZERO);
+        for _ in 0..3 {
+            sys.step(Duration::from_secs(5));
+        }
+
+        let stats = sys.stats("testing", 0).unwrap();
+        assert!(stats.count == 3, "Actual: {:?}", stats);
+        assert!(stats.total_run_time >= Duration::from_millis(60), "Actual: {:?}", stats);
+        assert!(stats.min_run_time >= Duration::from_millis(20), "Actual: {:?}", stats);
+        assert!(stats.max_run_time >= stats.min_run_time);
+        assert!(stats.average_run_time() >= Duration::from_millis(20));
+        assert!(sys.stats("testing", 1).is_none());
+        sys.drop_join_category("testing");
+
+        #[cfg(target_pointer_width = "64")]
+        {
+            let many = SystemStats { count: 1 << 32, total_run_time: Duration::from_secs(3 << 32), ..Default::default() };
+            assert!(many.average_run_time() == Duration::from_secs(3), "Actual: {:?}", many.average_run_time());
+        }
+    }
+
+    #[test]
+    fn test_for_each_handle() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("a", &ATOMIC, |_| ());
+        sys.register_static("a", &ATOMIC, |_| ());
+        sys.register_static("b", &ATOMIC, |_| ());
+        sys.register_inline("inline", &ATOMIC, |_| ());
+        sys.signal_all();
+        sys.signal("b");
+        sys.poll_once();
+        assert_invocations(&sys, "a", 0, 1);
+        assert_invocations(&sys, "a", 1, 1);
+        assert_invocations(&sys, "b", 0, 2);
+
+        let mut visited = Vec::new();
+        let mut total = 0;
+        sys.for_each_handle(|category, index, stats| {
+            visited.push((String::from(category), index));
+            total += stats.count;
+        });
+        let expected: usize = [("a", 0), ("a", 1), ("b", 0), ("inline", 0)].iter()
+            .map(|(category, index)| sys.stats(category, *index).unwrap().count)
+            .sum();
+        assert!(total == expected && total == 5, "Actual: {} ; Expected: {}", total, expected);
+        assert!(visited == vec![(String::from("a"), 0), (String::from("a"), 1), (String::from("b"), 0), (String::from("inline"), 0)]);
+        sys.clear();
+    }
+
+    #[test]
+    fn test_category_cpu_time() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        const SLEEP: Duration = Duration::from_millis(20);
+
+        sys.register_static("testing", &ATOMIC, |_| thread::sleep(SLEEP));
+        sys.register_static("testing", &ATOMIC, |_| thread::sleep(SLEEP));
+        sys.register_static("idle", &ATOMIC, |_| ());
+        for _ in 0..3 {
+            sys.signal("testing");
+        }
+        assert_invocations(&sys, "testing", 0, 3);
+        assert_invocations(&sys, "testing", 1, 3);
+
+        let total = sys.category_cpu_time("testing");
+        let single = sys.stats("testing", 0).unwrap().total_run_time;
+        assert!(total >= SLEEP * 2 * 3, "Actual: {:?}", total);
+        let ratio = total.as_secs_f64() / single.as_secs_f64();
+        assert!(ratio > 1.5 && ratio < 2.5, "Actual: {:?} ; Single: {:?}", total, single);
+        assert!(total == single + sys.stats("testing", 1).unwrap().total_run_time, "Actual: {:?}", total);
+        assert!(sys.category_cpu_time("idle") < SLEEP);
+        assert!(sys.category_cpu_time("missing") == Duration::ZERO);
+        sys.clear();
+    }
+
+    #[test]
+    fn test_lifetime_counts() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        for round in 0..5 {
+            sys.register_static("a", &ATOMIC, |_| ());
+            sys.register_static("a", &ATOMIC, |_| ());
+            sys.register_worker_pool("pool", &ATOMIC, |_, ()| (), 2);
+            sys.register_inline("inline", &ATOMIC, |_| ());
+            assert!(sys.lifetime_spawn_count() == 4 * (round + 1), "Actual: {:?}", sys.lifetime_spawn_count());
+            assert!(sys.lifetime_join_count() == 4 * round, "Actual: {:?}", sys.lifetime_join_count());
+            sys.drop_join_category("a");
+            sys.clear();
+        }
+        assert!(sys.lifetime_spawn_count() == sys.lifetime_join_count() && sys.lifetime_join_count() == 20);
+    }
+}
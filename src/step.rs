@@ -0,0 +1,224 @@
+use std::time::{Duration, Instant};
+
+use super::graph::{wait_for, wait_for_until};
+use super::{EcstaticError, EcstaticSystems};
+
+/// The outcome of a single `step`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StepReport {
+    /// How many systems finished their signal within the budget.
+    pub completed: usize,
+    /// Whether any system was still running when the budget ran out.
+    pub overran: bool,
+}
+
+impl EcstaticSystems {
+    /// Signals every system once and waits for them to finish, giving up once `budget` has elapsed.
+    /// Systems which overrun are left running and will finish in the background. Handles without a thread of
+    /// their own are signaled but not waited on, and are not counted in the report.
+    pub fn step(&self, budget: Duration) -> StepReport {
+        if self.is_frozen() {
+            return StepReport { completed: 0, overran: false };
+        }
+        let start = Instant::now();
+        let deadline = start + budget;
+        let targets: Vec<_> = self.handles.iter()
+            .flat_map(|(category, ths)| ths.iter().enumerate().map(move |(index, th)| (category, index, th)))
+            .filter_map(|(category, index, th)| self.signal_handle(category, index, th).map(|target| (th, target)))
+            .filter(|(th, _)| th.join_handle.is_some())
+            .collect();
+        let completed = targets.iter().filter(|(th, target)| th.state.wait_until(*target, deadline)).count();
+        if completed == targets.len() {
+            self.tick_latency.lock().unwrap().record(start.elapsed());
+        }
+        StepReport {
+            completed,
+            overran: completed < targets.len(),
+        }
+    }
+
+    /// Signals every handle of each category in `order` once, one at a time, waiting for each to finish before
+    /// signaling the next. Handles without a thread of their own are signaled but not waited on.
+    /// Nothing is signaled if any category in `order` is unknown.
+    pub fn tick_once_sync(&self, order: &[&str]) -> Result<(), EcstaticError> {
+        if let Some(category) = order.iter().find(|category| !self.handles.contains_key(**category)) {
+            return Err(EcstaticError::UnknownCategory(String::from(*category)));
+        }
+        if self.is_frozen() {
+            return Ok(());
+        }
+        for category in order {
+            for (index, th) in self.handles[*category].iter().enumerate() {
+                if let Some(target) = self.signal_handle(category, index, th) {
+                    wait_for(th, target);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Blocks until every handle in a category has finished the signals delivered to it before this call, or its
+    /// worker has exited. Returns `EcstaticError::Timeout` if any handle is still busy after `timeout`.
+    /// Handles without a thread of their own are not waited on.
+    pub fn wait_category_idle(&self, category: &str, timeout: Duration) -> Result<(), EcstaticError> {
+        let ths = self.handles.get(category).ok_or_else(|| EcstaticError::UnknownCategory(String::from(category)))?;
+        let deadline = Instant::now() + timeout;
+        let targets: Vec<_> = ths.iter().map(|th| (th, th.state.signaled_count())).collect();
+        if targets.iter().all(|(th, target)| wait_for_until(th, *target, deadline)) {
+            Ok(())
+        } else {
+            Err(EcstaticError::Timeout(String::from(category)))
+        }
+    }
+
+    /// Signals every handle once in registration order and waits for all of them to finish, so systems can fill
+    /// caches or initialize lazily before the main loop starts. Handles without a thread of their own are signaled
+    /// but not waited on. Returns an `EcstaticError::Panicked` for every signaled system which panicked.
+    pub fn warmup(&self) -> Vec<EcstaticError> {
+        if self.is_frozen() {
+            return Vec::new();
+        }
+        let targets: Vec<_> = self.order.iter()
+            .flat_map(|category| self.handles[category].iter().enumerate().map(move |(index, th)| (category, index, th)))
+            .filter_map(|(category, index, th)| {
+                let panics = th.state.panics();
+                self.signal_handle(category, index, th).map(|target| (category, index, th, target, panics))
+            })
+            .collect();
+        let mut errors = Vec::new();
+        for (category, index, th, target, panics) in targets {
+            wait_for(th, target);
+            if th.state.panics() > panics {
+                errors.push(EcstaticError::Panicked { category: category.clone(), index });
+            }
+        }
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    use super::StepReport;
+    use crate::{EcstaticError, EcstaticSystems};
+
+    #[test]
+    fn test_step_within_budget() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("testing2", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+
+        sys.register_inline("inline", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_main_thread("main", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+
+        let report = sys.step(Duration::from_secs(5));
+        assert!(report == StepReport { completed: 2, overran: false }, "Actual: {:?}", report);
+        assert!(ATOMIC.load(Ordering::Relaxed) == 2);
+        assert!(sys.tick_latency_stats().count == 1);
+        assert!(sys.poll_once() == 1 && sys.pump_main() == 1);
+        sys.clear();
+    }
+
+    #[test]
+    fn test_step_overran() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("fast", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("slow", &ATOMIC, |x|{
+            thread::sleep(Duration::from_millis(500));
+            x.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let report = sys.step(Duration::from_millis(50));
+        assert!(report == StepReport { completed: 1, overran: true }, "Actual: {:?}", report);
+        sys.clear();
+        assert!(ATOMIC.load(Ordering::Relaxed) == 2);
+    }
+
+    struct Logged {
+        name: &'static str,
+        log: &'static Mutex<Vec<&'static str>>,
+    }
+
+    fn log_after_sleep(x: Arc<&Logged>) {
+        thread::sleep(Duration::from_millis(10));
+        x.log.lock().unwrap().push(x.name);
+    }
+
+    #[test]
+    fn test_tick_once_sync() {
+        let mut sys = EcstaticSystems::new();
+        static LOG: Mutex<Vec<&str>> = Mutex::new(Vec::new());
+        static A0: Logged = Logged { name: "a0", log: &LOG };
+        static A1: Logged = Logged { name: "a1", log: &LOG };
+        static B0: Logged = Logged { name: "b0", log: &LOG };
+        static C0: Logged = Logged { name: "c0", log: &LOG };
+        static C1: Logged = Logged { name: "c1", log: &LOG };
+
+        for (category, logged) in [("a", &A0), ("a", &A1), ("b", &B0), ("c", &C0), ("c", &C1)].iter() {
+            sys.register_static(category, *logged, log_after_sleep);
+        }
+        assert!(sys.tick_once_sync(&["c", "missing", "a"]) == Err(EcstaticError::UnknownCategory(String::from("missing"))));
+        sys.tick_once_sync(&["c", "a", "b"]).unwrap();
+
+        assert!(*LOG.lock().unwrap() == vec!["c0", "c1", "a0", "a1", "b0"], "Actual: {:?}", *LOG.lock().unwrap());
+        for (category, index) in [("a", 0), ("a", 1), ("b", 0), ("c", 0), ("c", 1)].iter() {
+            assert!(sys.stats(category, *index).unwrap().count == 1);
+        }
+        sys.clear();
+        assert!(LOG.lock().unwrap().len() == 5);
+    }
+
+    #[test]
+    fn test_warmup() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        static ATOMIC1: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("a", &ATOMIC, |x|{
+            thread::sleep(Duration::from_millis(20));
+            x.fetch_add(1, Ordering::SeqCst);
+        });
+        sys.register_static("b", &ATOMIC1, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("b", &ATOMIC1, |_| panic!("warmup panicked"));
+        let errors = sys.warmup();
+
+        assert!(errors == vec![EcstaticError::Panicked { category: String::from("b"), index: 1 }], "Actual: {:?}", errors);
+        assert!(ATOMIC.load(Ordering::SeqCst) == 1);
+        assert!(ATOMIC1.load(Ordering::SeqCst) == 1);
+        assert!(sys.stats("a", 0).unwrap().count == 1 && sys.stats("b", 0).unwrap().count == 1);
+
+        sys.signal_all();
+        sys.clear();
+        assert!(ATOMIC.load(Ordering::SeqCst) == 2);
+        assert!(ATOMIC1.load(Ordering::SeqCst) == 2);
+    }
+
+    #[test]
+    fn test_wait_category_idle() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("backlog", &ATOMIC, |x|{
+            thread::sleep(Duration::from_millis(10));
+            x.fetch_add(1, Ordering::SeqCst);
+        });
+        sys.register_static("other", &ATOMIC, |_| thread::sleep(Duration::from_millis(200)));
+        sys.signal("other");
+        for _ in 0..10 {
+            sys.signal("backlog");
+        }
+        assert!(sys.wait_category_idle("backlog", Duration::from_millis(5)) == Err(EcstaticError::Timeout(String::from("backlog"))));
+        assert!(sys.wait_category_idle("backlog", Duration::from_secs(5)) == Ok(()));
+        assert!(ATOMIC.load(Ordering::SeqCst) == 10);
+        assert!(sys.wait_category_idle("missing", Duration::ZERO) == Err(EcstaticError::UnknownCategory(String::from("missing"))));
+        sys.clear();
+    }
+}
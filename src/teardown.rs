@@ -0,0 +1,178 @@
+use std::mem;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::{EcstaticSystems, ExitReason, ThreadHandle};
+
+/// How often `clear_timeout` checks whether the remaining workers have exited.
+const JOIN_POLL: Duration = Duration::from_millis(1);
+
+impl EcstaticSystems {
+    /// Sets the priority a category is joined with by `clear` and on drop. Higher priorities are joined first
+    /// and categories are priority 0 until assigned.
+    pub fn set_teardown_priority(&mut self, category: &str, priority: i32) {
+        self.configs.entry(String::from(category)).or_default().teardown_priority = priority;
+    }
+
+    /// The category `clear` should join next, if any remain. `max_by_key` keeps the last of equal elements,
+    /// which is the most recently registered category.
+    pub(crate) fn next_teardown(&self) -> Option<String> {
+        let priority = |category: &String| self.configs.get(category).map_or(0, |config| config.teardown_priority);
+        self.order.iter().max_by_key(|category| priority(category)).cloned()
+    }
+
+    /// Drops every category like `clear` and returns how long each one took to join, in the order they were joined.
+    pub fn clear_timed(&mut self) -> Vec<(String, Duration)> {
+        let mut timings = Vec::new();
+        while let Some(category) = self.next_teardown() {
+            let start = Instant::now();
+            self.drop_join_category(&category);
+            timings.push((category, start.elapsed()));
+        }
+        timings
+    }
+
+    /// Drops every category like `clear`, joining them in teardown priority order, but only waits up to `timeout` in total
+    /// for workers to exit. A category's senders are dropped once every category joined before it has exited or the
+    /// deadline has passed. Workers still running at the deadline are detached and returned, in the order their
+    /// categories were joined, and have no exit reason.
+    pub fn clear_timeout(&mut self, timeout: Duration) -> Vec<(String, usize)> {
+        let deadline = Instant::now() + timeout;
+        let mut stuck = Vec::new();
+        while let Some(category) = self.next_teardown() {
+            self.order.retain(|c| *c != category);
+            self.cancel_category_schedules(&category);
+            self.aggregators.remove(&category);
+            let mut ths = self.handles.remove(&category).unwrap_or_default();
+            for th in ths.iter_mut() {
+                if let Some(token) = th.cancel.as_ref() {
+                    token.cancel();
+                }
+                mem::drop(th.mx.take());
+                mem::drop(th.sx.take());
+            }
+
+            let finished = |ths: &[ThreadHandle]| {
+                ths.iter().filter_map(|th| th.join_handle.as_ref()).all(|join_handle| join_handle.is_finished())
+            };
+            while !finished(&ths) && Instant::now() < deadline {
+                thread::sleep(JOIN_POLL);
+            }
+
+            let mut reasons = Vec::new();
+            for (index, mut th) in ths.into_iter().enumerate() {
+                let reason = match th.join_handle.take() {
+                    Some(join_handle) if join_handle.is_finished() => {
+                        let result = join_handle.join();
+                        self.joined.fetch_add(1, Ordering::Relaxed);
+                        match result {
+                            Ok(()) => th.state.exit_reason(),
+                            Err(_) => Some(ExitReason::Aborted),
+                        }
+                    }
+                    Some(_) => {
+                        stuck.push((category.clone(), index));
+                        None
+                    }
+                    None => None,
+                };
+                reasons.push(reason);
+            }
+            self.exit_reasons.insert(category, reasons);
+        }
+        stuck
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crate::test_util::pump_until;
+    use crate::{EcstaticSystems, ExitReason};
+
+    static JOINED: Mutex<Vec<&str>> = Mutex::new(Vec::new());
+
+    fn record(category: &'static str) {
+        JOINED.lock().unwrap().push(category);
+    }
+
+    #[test]
+    fn test_teardown_priority() {
+        let mut sys = EcstaticSystems::new();
+
+        sys.register_stateful("assets", || "assets", |_| (), record);
+        sys.register_stateful("render", || "render", |_| (), record);
+        sys.register_stateful("audio", || "audio", |_| (), record);
+        sys.register_stateful("input", || "input", |_| (), record);
+        sys.set_teardown_priority("render", 10);
+        sys.set_teardown_priority("assets", 5);
+        sys.set_teardown_priority("input", -1);
+        sys.clear();
+
+        let joined = JOINED.lock().unwrap().clone();
+        assert!(joined == vec!["render", "assets", "audio", "input"], "Actual: {:?}", joined);
+    }
+
+    #[test]
+    fn test_clear_timed() {
+        let mut sys = EcstaticSystems::new();
+
+        sys.register_stateful("fast", || "fast", |_| (), |_| ());
+        sys.register_stateful("slow", || "slow", |_| (), |_| thread::sleep(Duration::from_millis(100)));
+        sys.set_teardown_priority("slow", 1);
+        let timings = sys.clear_timed();
+
+        let categories: Vec<&str> = timings.iter().map(|(category, _)| category.as_str()).collect();
+        assert!(categories == vec!["slow", "fast"], "Actual: {:?}", timings);
+        assert!(timings[0].1 >= Duration::from_millis(100), "Actual: {:?}", timings);
+        assert!(timings[0].1 > timings[1].1, "Actual: {:?}", timings);
+        assert!(sys.clear_timed().is_empty());
+    }
+
+    static HUNG: AtomicBool = AtomicBool::new(true);
+
+    fn hang(x: Arc<&AtomicUsize>) {
+        x.fetch_add(1, Ordering::SeqCst);
+        while HUNG.load(Ordering::SeqCst) {
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn test_clear_timeout() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        static STARTED: AtomicUsize = AtomicUsize::new(0);
+
+        static EXITED: Mutex<Vec<&str>> = Mutex::new(Vec::new());
+
+        sys.register_static("first", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_stateful("first", || "first", |_| (), |name| EXITED.lock().unwrap().push(name));
+        sys.register_static("hung", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("hung", &STARTED, hang);
+        sys.register_static("last", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_stateful("last", || "last", |_| thread::sleep(Duration::from_millis(20)), |name| EXITED.lock().unwrap().push(name));
+        sys.set_teardown_priority("last", 1);
+        sys.set_teardown_priority("hung", -1);
+        sys.signal_all();
+        pump_until(&sys, |_| STARTED.load(Ordering::SeqCst) == 1);
+
+        let start = Instant::now();
+        let stuck = sys.clear_timeout(Duration::from_millis(100));
+        assert!(start.elapsed() < Duration::from_secs(2), "Took: {:?}", start.elapsed());
+        assert!(stuck == vec![(String::from("hung"), 1)], "Actual: {:?}", stuck);
+        assert!(ATOMIC.load(Ordering::SeqCst) == 3);
+        assert!(*EXITED.lock().unwrap() == vec!["last", "first"], "Actual: {:?}", EXITED.lock().unwrap());
+        assert!(!sys.has_category("first") && !sys.has_category("hung") && !sys.has_category("last"));
+        assert!(sys.exit_reason("first", 0) == Some(ExitReason::Normal));
+        assert!(sys.exit_reason("hung", 0) == Some(ExitReason::Normal));
+        assert!(sys.exit_reason("hung", 1).is_none());
+        assert!(sys.exit_reason("last", 0) == Some(ExitReason::Normal));
+        HUNG.store(false, Ordering::SeqCst);
+    }
+}
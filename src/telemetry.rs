@@ -0,0 +1,115 @@
+//! Emits system metrics through the `metrics` crate, so they reach whichever recorder the application installed.
+//! Every metric is labeled with the `category` and `index` of the handle it describes.
+
+use std::time::Duration;
+
+use metrics::{counter, gauge, histogram};
+
+pub(crate) const INVOCATIONS: &str = "ecstatic_system_invocations_total";
+pub(crate) const PANICS: &str = "ecstatic_system_panics_total";
+pub(crate) const QUEUE_DEPTH: &str = "ecstatic_system_queue_depth";
+pub(crate) const RUN_DURATION: &str = "ecstatic_system_run_duration_seconds";
+
+pub(crate) struct Labels {
+    category: String,
+    index: String,
+}
+
+impl Labels {
+    pub(crate) fn new(category: &str, index: usize) -> Labels {
+        Labels { category: String::from(category), index: index.to_string() }
+    }
+
+    pub(crate) fn invoked(&self, run_time: Duration) {
+        counter!(INVOCATIONS, "category" => self.category.clone(), "index" => self.index.clone()).increment(1);
+        histogram!(RUN_DURATION, "category" => self.category.clone(), "index" => self.index.clone()).record(run_time.as_secs_f64());
+    }
+
+    pub(crate) fn panicked(&self) {
+        counter!(PANICS, "category" => self.category.clone(), "index" => self.index.clone()).increment(1);
+    }
+
+    pub(crate) fn queue_depth(&self, depth: usize) {
+        gauge!(QUEUE_DEPTH, "category" => self.category.clone(), "index" => self.index.clone()).set(depth as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use metrics::{Counter, CounterFn, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit};
+
+    use super::{INVOCATIONS, PANICS};
+    use crate::test_util::{assert_invocations, pump_until};
+    use crate::EcstaticSystems;
+
+    type Counts = Arc<Mutex<HashMap<String, u64>>>;
+
+    /// Counts every counter increment by metric name and labels, e.g. `name{category=testing,index=0}`.
+    #[derive(Default)]
+    struct CountingRecorder {
+        counts: Counts,
+    }
+
+    struct KeyCounter {
+        key: String,
+        counts: Counts,
+    }
+
+    impl CounterFn for KeyCounter {
+        fn increment(&self, value: u64) {
+            *self.counts.lock().unwrap().entry(self.key.clone()).or_default() += value;
+        }
+
+        fn absolute(&self, value: u64) {
+            self.counts.lock().unwrap().insert(self.key.clone(), value);
+        }
+    }
+
+    impl Recorder for CountingRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            let labels: Vec<String> = key.labels().map(|label| format!("{}={}", label.key(), label.value())).collect();
+            let key = format!("{}{{{}}}", key.name(), labels.join(","));
+            Counter::from_arc(Arc::new(KeyCounter { key, counts: self.counts.clone() }))
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    #[test]
+    fn test_metrics_counters() {
+        let recorder = CountingRecorder::default();
+        let counts = recorder.counts.clone();
+        metrics::set_global_recorder(recorder).unwrap();
+        let count = |name: &str, category: &str, index: usize| {
+            counts.lock().unwrap().get(&format!("{}{{category={},index={}}}", name, category, index)).copied().unwrap_or(0)
+        };
+
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        sys.register_static("metered", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("metered", &ATOMIC, |_| panic!("metered panic"));
+        for _ in 0..3 {
+            sys.signal("metered");
+        }
+        assert_invocations(&sys, "metered", 0, 3);
+        pump_until(&sys, |_| count(PANICS, "metered", 1) == 1);
+        sys.clear();
+
+        assert!(count(INVOCATIONS, "metered", 0) == 3, "Actual: {:?}", counts.lock().unwrap());
+        assert!(count(INVOCATIONS, "metered", 1) == 0, "Actual: {:?}", counts.lock().unwrap());
+    }
+}
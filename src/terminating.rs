@@ -0,0 +1,48 @@
+use std::any::Any;
+use std::marker::{Send, Sync};
+use std::sync::Arc;
+
+use super::{data_slot, EcstaticSystems};
+
+impl EcstaticSystems {
+    /// Registers a system like `register_static` which decides its own lifetime. The worker thread exits
+    /// as soon as `f` returns false, and any later signals to it are dropped.
+    pub fn register_self_terminating<'a: 'static, T: Any + Send + Sync>(&mut self, category: &str, data: &'a T, f: fn(Arc<&'a T>) -> bool) {
+        if self.is_shutting_down() {
+            return;
+        }
+        let data = data_slot(data);
+        let worker_data = data.clone();
+        let mut th = self.system_create_with(category, || (), move |_| f(worker_data.load_full()), |_| ());
+        th.data = Some(data);
+        self.push_handle(category, th);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::test_util::pump_until;
+    use crate::{EcstaticSystems, HandleStatus};
+
+    #[test]
+    fn test_self_terminating() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_self_terminating("testing", &ATOMIC, |x| x.fetch_add(1, Ordering::SeqCst) + 1 < 3);
+        sys.register_inline("inline", &ATOMIC, |_| ());
+        assert!(sys.handle_status("testing", 0) == Ok(HandleStatus::Running));
+        assert!(sys.handle_status("inline", 0) == Ok(HandleStatus::Inline));
+        for _ in 0..3 {
+            sys.signal("testing");
+        }
+        pump_until(&sys, |sys| sys.handle_status("testing", 0) == Ok(HandleStatus::Exited));
+
+        assert!(sys.handles["testing"][0].sx.is_some());
+        sys.signal("testing");
+        sys.clear();
+        assert!(ATOMIC.load(Ordering::SeqCst) == 3, "Actual: {:?}", ATOMIC.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,102 @@
+//! Helpers for testing systems without hand rolled sleeps or spin loops.
+//! Available to the crate's own tests, and to downstream tests through the `test-util` feature.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::EcstaticSystems;
+
+/// How long the helpers wait for a condition before failing the test.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Waits until `pred` holds, panicking if it still does not after `DEFAULT_TIMEOUT`.
+pub fn pump_until<F: Fn(&EcstaticSystems) -> bool>(sys: &EcstaticSystems, pred: F) {
+    let deadline = Instant::now() + DEFAULT_TIMEOUT;
+    while !pred(sys) {
+        if Instant::now() >= deadline {
+            panic!("condition was not met within {:?}", DEFAULT_TIMEOUT);
+        }
+        thread::yield_now();
+    }
+}
+
+/// Waits for a handle to finish `expected` invocations, then asserts that it has finished exactly that many.
+pub fn assert_invocations(sys: &EcstaticSystems, category: &str, index: usize, expected: usize) {
+    let invocations = |sys: &EcstaticSystems| sys.handle(category, index).map(|th| th.state.invocations());
+    let deadline = Instant::now() + DEFAULT_TIMEOUT;
+    while invocations(sys).map(|actual| actual < expected).unwrap_or(false) && Instant::now() < deadline {
+        thread::yield_now();
+    }
+    match invocations(sys) {
+        Ok(actual) => assert!(actual == expected, "handle {} in category \"{}\" ran {} times ; Expected: {}", index, category, actual, expected),
+        Err(e) => panic!("{}", e),
+    }
+}
+
+/// Data for a system which blocks until released, so tests can fill its buffer behind a running invocation.
+pub struct Gated {
+    /// Lets every invocation, current and future, finish.
+    pub released: AtomicBool,
+    /// How many invocations have started.
+    pub runs: AtomicUsize,
+}
+
+impl Gated {
+    pub const fn new() -> Gated {
+        Gated { released: AtomicBool::new(false), runs: AtomicUsize::new(0) }
+    }
+}
+
+impl Default for Gated {
+    fn default() -> Gated {
+        Gated::new()
+    }
+}
+
+/// A system which counts its run in `runs` and then spins until `released` is set.
+pub fn gated(x: Arc<&Gated>) {
+    x.runs.fetch_add(1, Ordering::SeqCst);
+    while !x.released.load(Ordering::SeqCst) {
+        thread::yield_now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::{assert_invocations, pump_until};
+    use crate::EcstaticSystems;
+
+    #[test]
+    fn test_helpers() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.signal("testing");
+        sys.signal("testing");
+        sys.signal("testing");
+
+        assert_invocations(&sys, "testing", 0, 3);
+        assert_invocations(&sys, "testing", 1, 3);
+        pump_until(&sys, |_| ATOMIC.load(Ordering::SeqCst) == 6);
+        sys.drop_join_category("testing");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_assert_invocations_too_many() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.signal("testing");
+        sys.signal("testing");
+        pump_until(&sys, |_| ATOMIC.load(Ordering::SeqCst) == 2);
+        assert_invocations(&sys, "testing", 0, 1);
+    }
+}
@@ -0,0 +1,94 @@
+use super::{AliasMode, EcstaticError, EcstaticSystems};
+
+/// Signals staged by `begin_transaction`. Nothing reaches a worker until `commit` is called,
+/// and dropping the transaction without committing discards every staged signal.
+#[derive(Debug, Default)]
+pub struct SignalTransaction {
+    staged: Vec<String>,
+}
+
+impl SignalTransaction {
+    /// Stages a signal to a category, sent like `EcstaticSystems::signal` on commit.
+    pub fn signal(&mut self, category: &str) {
+        self.staged.push(String::from(category));
+    }
+
+    /// How many signals are staged.
+    pub fn len(&self) -> usize {
+        self.staged.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.staged.is_empty()
+    }
+
+    /// Sends every staged signal in the order it was staged. If any staged category is not registered
+    /// nothing is sent and the first such category is returned as `EcstaticError::UnknownCategory`.
+    pub fn commit(self, sys: &EcstaticSystems) -> Result<(), EcstaticError> {
+        if let Some(category) = self.staged.iter().find(|category| !sys.can_signal(category)) {
+            return Err(EcstaticError::UnknownCategory(category.clone()));
+        }
+        for category in self.staged.iter() {
+            sys.signal(category);
+        }
+        Ok(())
+    }
+}
+
+impl EcstaticSystems {
+    /// Starts staging signals which are sent as a unit once committed, see `SignalTransaction`.
+    pub fn begin_transaction(&self) -> SignalTransaction {
+        SignalTransaction::default()
+    }
+
+    /// Whether `signal` can be called on a category without panicking, following any alias.
+    fn can_signal(&self, category: &str) -> bool {
+        match self.aliases.get(category) {
+            Some((to, AliasMode::Redirect)) => self.has_category(to),
+            Some((to, AliasMode::Mirror)) => self.has_category(category) && self.has_category(to),
+            None => self.has_category(category),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::test_util::assert_invocations;
+    use crate::{EcstaticError, EcstaticSystems};
+
+    #[test]
+    fn test_signal_transaction() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        static ATOMIC1: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("a", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("b", &ATOMIC1, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        {
+            let mut tx = sys.begin_transaction();
+            tx.signal("a");
+            tx.signal("b");
+            tx.signal("a");
+            assert!(tx.len() == 3);
+        }
+        let mut tx = sys.begin_transaction();
+        tx.signal("a");
+        tx.signal("missing");
+        assert!(tx.commit(&sys) == Err(EcstaticError::UnknownCategory(String::from("missing"))));
+
+        let mut tx = sys.begin_transaction();
+        tx.signal("b");
+        tx.signal("b");
+        sys.signal("a");
+        assert_invocations(&sys, "a", 0, 1);
+        assert!(sys.stats("b", 0).unwrap().count == 0);
+        tx.commit(&sys).unwrap();
+        assert_invocations(&sys, "b", 0, 2);
+        sys.clear();
+
+        assert!(ATOMIC.load(Ordering::SeqCst) == 1, "Actual: {:?}", ATOMIC.load(Ordering::SeqCst));
+        assert!(ATOMIC1.load(Ordering::SeqCst) == 2, "Actual: {:?}", ATOMIC1.load(Ordering::SeqCst));
+    }
+}
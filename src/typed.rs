@@ -0,0 +1,152 @@
+use std::any::Any;
+use std::marker::{PhantomData, Send, Sync};
+use std::sync::Arc;
+
+use super::EcstaticSystems;
+
+/// A fixed set of categories, usually an enum, so that category names are checked at compile time.
+pub trait Category {
+    fn name(&self) -> &'static str;
+}
+
+/// Wraps `EcstaticSystems` so every category is a variant of `C` instead of a string.
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use ecstatic_system::{Category, TypedSystems};
+///
+/// enum Stage { Physics, Render }
+///
+/// impl Category for Stage {
+///     fn name(&self) -> &'static str {
+///         match self {
+///             Stage::Physics => "physics",
+///             Stage::Render => "render",
+///         }
+///     }
+/// }
+///
+/// static FRAMES: AtomicUsize = AtomicUsize::new(0);
+/// let mut sys = TypedSystems::new();
+/// sys.register_static(Stage::Render, &FRAMES, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+/// sys.signal(Stage::Render);
+/// sys.drop_join_category(Stage::Render);
+/// assert!(FRAMES.load(Ordering::SeqCst) == 1);
+/// # let _ = Stage::Physics;
+/// ```
+///
+/// Misspelled categories do not compile:
+///
+/// ```compile_fail
+/// use ecstatic_system::{Category, TypedSystems};
+///
+/// enum Stage { Render }
+///
+/// impl Category for Stage {
+///     fn name(&self) -> &'static str {
+///         "render"
+///     }
+/// }
+///
+/// let sys: TypedSystems<Stage> = TypedSystems::new();
+/// sys.signal(Stage::Rendr);
+/// ```
+pub struct TypedSystems<C: Category> {
+    inner: EcstaticSystems,
+    categories: PhantomData<fn(C)>,
+}
+
+impl<C: Category> Default for TypedSystems<C> {
+    fn default() -> TypedSystems<C> {
+        TypedSystems::from(EcstaticSystems::new())
+    }
+}
+
+impl<C: Category> From<EcstaticSystems> for TypedSystems<C> {
+    fn from(inner: EcstaticSystems) -> TypedSystems<C> {
+        TypedSystems { inner, categories: PhantomData }
+    }
+}
+
+impl<C: Category> TypedSystems<C> {
+    pub fn new() -> TypedSystems<C> {
+        TypedSystems::default()
+    }
+
+    pub fn signal_all(&self) {
+        self.inner.signal_all();
+    }
+
+    pub fn signal(&self, category: C) {
+        self.inner.signal(category.name());
+    }
+
+    pub fn has_category(&self, category: C) -> bool {
+        self.inner.has_category(category.name())
+    }
+
+    pub fn register_static<'a: 'static, T: Any + Send + Sync>(&mut self, category: C, data: &'a T, f: fn(Arc<&'a T>)) {
+        self.inner.register_static(category.name(), data, f);
+    }
+
+    pub fn drop_join_category(&mut self, category: C) {
+        self.inner.drop_join_category(category.name());
+    }
+
+    /// The string based manager, for everything the typed wrapper does not cover.
+    pub fn inner(&self) -> &EcstaticSystems {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut EcstaticSystems {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> EcstaticSystems {
+        self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::{Category, TypedSystems};
+    use crate::test_util::assert_invocations;
+
+    #[derive(Clone, Copy)]
+    enum Stage {
+        Input,
+        Physics,
+    }
+
+    impl Category for Stage {
+        fn name(&self) -> &'static str {
+            match self {
+                Stage::Input => "input",
+                Stage::Physics => "physics",
+            }
+        }
+    }
+
+    #[test]
+    fn test_typed_systems() {
+        let mut sys = TypedSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+        static ATOMIC1: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static(Stage::Input, &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static(Stage::Physics, &ATOMIC1, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.signal(Stage::Input);
+        sys.signal_all();
+        assert_invocations(sys.inner(), "input", 0, 2);
+        assert_invocations(sys.inner(), "physics", 0, 1);
+
+        sys.drop_join_category(Stage::Physics);
+        assert!(!sys.has_category(Stage::Physics));
+        assert!(sys.has_category(Stage::Input));
+        let mut inner = sys.into_inner();
+        inner.clear();
+        assert!(ATOMIC.load(Ordering::SeqCst) == 2 && ATOMIC1.load(Ordering::SeqCst) == 1);
+    }
+}
@@ -0,0 +1,153 @@
+use std::sync::mpsc::TrySendError;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::{EcstaticError, EcstaticSystems, EcstaticSystemsBuilder, ErrorEvent, ThreadHandle};
+
+/// How long a watched signal sleeps between attempts while the handle's buffer is full.
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+impl EcstaticSystemsBuilder {
+    /// Reports an `ErrorEvent::SignalBlocked` to the error sink whenever a signal blocks on a full handle
+    /// for longer than `threshold`, which usually means the handle's worker is wedged.
+    pub fn signal_watchdog(mut self, threshold: Duration) -> EcstaticSystemsBuilder {
+        self.watchdog = Some(threshold);
+        self
+    }
+}
+
+impl EcstaticSystems {
+    /// Sends a blocking signal to one handle, watched by the signal watchdog if there is one.
+    /// Returns the handle's signal count like `ThreadHandle::send_signal`.
+    /// Signals sent by a worker to its own full handle are dropped instead, see `signal_own`, as are signals
+    /// to a full handle without a thread of its own, see `signal_threadless`. Both are reported as `ErrorEvent::SignalDropped`.
+    pub(crate) fn signal_handle(&self, category: &str, index: usize, th: &ThreadHandle) -> Option<usize> {
+        if th.is_own_thread() {
+            return match signal_own(category, index, th) {
+                Ok(signaled) => Some(signaled),
+                Err(EcstaticError::Reentrancy { .. }) => {
+                    self.report_dropped(category, index);
+                    None
+                }
+                Err(_) => None,
+            };
+        }
+        if th.join_handle.is_none() {
+            return self.signal_threadless(category, index, th);
+        }
+        let (threshold, sink) = match (self.watchdog, self.error_sink.as_ref()) {
+            (Some(threshold), Some(sink)) => (threshold, sink),
+            _ => return th.send_signal(),
+        };
+        let sx = th.sx.as_ref()?;
+        let start = Instant::now();
+        loop {
+            match sx.try_send(()) {
+                Ok(()) => break,
+                Err(TrySendError::Disconnected(())) => return None,
+                Err(TrySendError::Full(())) if start.elapsed() >= threshold => {
+                    sink.report(&ErrorEvent::SignalBlocked { category: String::from(category), index, waited: start.elapsed() });
+                    sx.send(()).ok()?;
+                    break;
+                }
+                Err(TrySendError::Full(())) => thread::sleep(POLL_INTERVAL),
+            }
+        }
+        Some(th.state.signaled())
+    }
+
+    /// Signals a handle without a thread of its own, such as an inline or main thread system. Its buffer is only
+    /// drained by `poll_once` or `pump_main`, so blocking on a full buffer could wait forever and the signal is
+    /// dropped instead, reported to the error sink as `ErrorEvent::SignalDropped`.
+    fn signal_threadless(&self, category: &str, index: usize, th: &ThreadHandle) -> Option<usize> {
+        match th.sx.as_ref()?.try_send(()) {
+            Ok(()) => Some(th.state.signaled()),
+            Err(TrySendError::Full(())) => {
+                self.report_dropped(category, index);
+                None
+            }
+            Err(TrySendError::Disconnected(())) => None,
+        }
+    }
+
+    fn report_dropped(&self, category: &str, index: usize) {
+        if let Some(sink) = self.error_sink.as_ref() {
+            sink.report(&ErrorEvent::SignalDropped { category: String::from(category), index });
+        }
+    }
+}
+
+/// Signals a handle from its own worker thread. Blocking on a full buffer would wait for the worker to receive,
+/// which it never can while it is sending, so a full buffer is reported as `EcstaticError::Reentrancy` instead.
+/// Zero capacity handles are always full while their worker runs.
+pub(crate) fn signal_own(category: &str, index: usize, th: &ThreadHandle) -> Result<usize, EcstaticError> {
+    let sx = th.sx.as_ref().ok_or_else(|| EcstaticError::Unsupported { category: String::from(category), index })?;
+    match sx.try_send(()) {
+        Ok(()) => Ok(th.state.signaled()),
+        Err(TrySendError::Full(())) => Err(EcstaticError::Reentrancy { category: String::from(category), index }),
+        Err(TrySendError::Disconnected(())) => Err(EcstaticError::Disconnected { category: String::from(category), index }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crate::test_util::{pump_until, DEFAULT_TIMEOUT};
+    use crate::{CategoryConfig, CollectingSink, EcstaticSystems, ErrorEvent};
+
+    static STARTED: AtomicBool = AtomicBool::new(false);
+    static RELEASED: AtomicBool = AtomicBool::new(false);
+
+    fn wedge(runs: Arc<&AtomicUsize>) {
+        STARTED.store(true, Ordering::SeqCst);
+        while !RELEASED.load(Ordering::SeqCst) {
+            thread::yield_now();
+        }
+        runs.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn test_signal_watchdog() {
+        let threshold = Duration::from_millis(50);
+        let sink = CollectingSink::new();
+        let mut sys = EcstaticSystems::builder().error_sink(sink.clone()).signal_watchdog(threshold).build();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.configure_category("testing", CategoryConfig { buffer_size: Some(1), ..Default::default() });
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_static("testing", &ATOMIC, wedge);
+        sys.signal("testing");
+        pump_until(&sys, |_| STARTED.load(Ordering::SeqCst));
+        sys.signal("testing");
+        assert!(sink.events().is_empty(), "Actual: {:?}", sink.events());
+
+        let watcher = sink.clone();
+        let release = thread::spawn(move || {
+            let deadline = Instant::now() + DEFAULT_TIMEOUT;
+            while watcher.events().is_empty() && Instant::now() < deadline {
+                thread::yield_now();
+            }
+            RELEASED.store(true, Ordering::SeqCst);
+        });
+        let start = Instant::now();
+        sys.signal("testing");
+        let blocked = start.elapsed();
+        release.join().unwrap();
+        sys.clear();
+
+        let events = sink.take();
+        assert!(events.len() == 1, "Actual: {:?}", events);
+        match &events[0] {
+            ErrorEvent::SignalBlocked { category, index, waited } => {
+                assert!(category == "testing" && *index == 1, "Actual: {:?}", events[0]);
+                assert!(*waited >= threshold && *waited <= blocked, "Waited: {:?} ; Blocked: {:?}", waited, blocked);
+            }
+            event => panic!("unexpected event {:?}", event),
+        }
+        assert!(ATOMIC.load(Ordering::SeqCst) == 6, "Actual: {:?}", ATOMIC.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::{Arc, Weak};
+
+use super::{EcstaticError, EcstaticSystems, HandleStatus, SystemState, SystemStats};
+
+/// A reference to a registered system which does not keep it alive, see `weak_system`.
+/// Once the system's category is joined every query returns `None`.
+#[derive(Clone)]
+pub struct WeakSystem {
+    state: Weak<SystemState>,
+    threaded: bool,
+}
+
+impl WeakSystem {
+    /// Whether the system has not been joined yet.
+    pub fn is_alive(&self) -> bool {
+        self.state.strong_count() > 0
+    }
+
+    /// Invocation counts and timings for the system, like `EcstaticSystems::stats`.
+    pub fn stats(&self) -> Option<SystemStats> {
+        Some(self.state.upgrade()?.stats())
+    }
+
+    /// Whether the system's worker is still running, like `EcstaticSystems::handle_status`.
+    /// A worker which unwound outside of its system reports `Running` until it is joined.
+    pub fn status(&self) -> Option<HandleStatus> {
+        let state = self.state.upgrade()?;
+        Some(match (self.threaded, state.exit_reason()) {
+            (false, _) => HandleStatus::Inline,
+            (true, Some(_)) => HandleStatus::Exited,
+            (true, None) if state.is_disabled() => HandleStatus::Disabled,
+            (true, None) => HandleStatus::Running,
+        })
+    }
+}
+
+impl EcstaticSystems {
+    /// A weak reference to a handle's system for observers which must not hold up joining or dropping it.
+    pub fn weak_system(&self, category: &str, index: usize) -> Result<WeakSystem, EcstaticError> {
+        let th = self.handle(category, index)?;
+        Ok(WeakSystem { state: Arc::downgrade(&th.state), threaded: th.join_handle.is_some() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::test_util::assert_invocations;
+    use crate::{EcstaticError, EcstaticSystems, HandleStatus};
+
+    #[test]
+    fn test_weak_system() {
+        let mut sys = EcstaticSystems::new();
+        static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+        sys.register_static("testing", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        sys.register_inline("inline", &ATOMIC, |x|{ x.fetch_add(1, Ordering::SeqCst); });
+        let weak = sys.weak_system("testing", 0).unwrap();
+        let inline = sys.weak_system("inline", 0).unwrap();
+        assert!(sys.weak_system("testing", 1).err() == Some(EcstaticError::UnknownHandle { category: String::from("testing"), index: 1 }));
+        sys.signal("testing");
+        assert_invocations(&sys, "testing", 0, 1);
+
+        assert!(weak.is_alive());
+        assert!(weak.stats().unwrap().count == 1);
+        assert!(weak.status() == Some(HandleStatus::Running));
+        assert!(inline.status() == Some(HandleStatus::Inline));
+
+        sys.drop_join_category("testing");
+        assert!(!weak.is_alive());
+        assert!(weak.stats().is_none() && weak.status().is_none());
+        assert!(inline.is_alive());
+        mem::drop(sys);
+        assert!(!inline.is_alive());
+        assert!(ATOMIC.load(Ordering::SeqCst) == 1);
+    }
+}